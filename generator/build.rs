@@ -517,7 +517,12 @@ impl Parameter {
                 self.param_type
                     .default()
             ),
-            ParamType::ArrayDouble | ParamType::ArrayInt | ParamType::ArrayImage => format!(
+            ParamType::ArrayDouble
+            | ParamType::ArrayInt
+            | ParamType::ArrayEnum {
+                ..
+            }
+            | ParamType::ArrayImage => format!(
                 "let mut {}_out: {} = Vec::new();",
                 self.name,
                 self.param_type
@@ -603,6 +608,9 @@ enum ParamType {
         default: bool,
     },
     ArrayInt,
+    ArrayEnum {
+        name: String,
+    },
     ArrayDouble,
     ArrayImage,
     ArrayByte,
@@ -695,6 +703,12 @@ impl ParamType {
                 ..
             } => String::from("bool"),
             ParamType::ArrayInt => String::from("Vec<i32>"),
+            ParamType::ArrayEnum {
+                name,
+            } => format!(
+                "Vec<{}>",
+                Self::enum_name(name)
+            ),
             ParamType::ArrayDouble => String::from("Vec<f64>"),
             ParamType::ArrayByte => String::from("Vec<u8>"),
             ParamType::ArrayImage => String::from("Vec<VipsImage>"),
@@ -747,6 +761,12 @@ impl ParamType {
                 }
             }
             ParamType::ArrayInt => String::from("&[i32]"),
+            ParamType::ArrayEnum {
+                name,
+            } => format!(
+                "&[{}]",
+                Self::enum_name(name)
+            ),
             ParamType::ArrayDouble => {
                 if is_output {
                     String::from("&mut Vec<f64>")
@@ -812,6 +832,12 @@ impl ParamType {
                 ..
             } => String::from("bool"),
             ParamType::ArrayInt => String::from("&[i32]"),
+            ParamType::ArrayEnum {
+                name,
+            } => format!(
+                "&[{}]",
+                Self::enum_name(name)
+            ),
             ParamType::ArrayDouble => String::from("&[f64]"),
             ParamType::ArrayByte => String::from("&[u8]"),
             ParamType::ArrayImage => String::from("&[VipsImage]"),
@@ -857,6 +883,12 @@ impl ParamType {
                 ..
             } => String::from("bool"),
             ParamType::ArrayInt => String::from("Vec<i32>"),
+            ParamType::ArrayEnum {
+                name,
+            } => format!(
+                "Vec<{}>",
+                Self::enum_name(name)
+            ),
             ParamType::ArrayDouble => String::from("Vec<f64>"),
             ParamType::ArrayByte => String::from("Vec<u8>"),
             _ => String::new(),
@@ -886,6 +918,9 @@ impl ParamType {
                 ..
             } => default.to_string(),
             ParamType::ArrayInt => String::from("Vec::new()"),
+            ParamType::ArrayEnum {
+                ..
+            } => String::from("Vec::new()"),
             ParamType::ArrayDouble => String::from("Vec::new()"),
             ParamType::ArrayByte => String::from("Vec::new()"),
             ParamType::ArrayImage => String::from("Vec::new()"),
@@ -934,9 +969,12 @@ impl ParamType {
                 pub enum {} {{
                     {}
                 }}
+
+                impl crate::voption::VipsEnum for {} {{}}
                 "#,
                     Self::enum_name(name),
-                    enum_entries
+                    enum_entries,
+                    Self::enum_name(name)
                 )
             }
             _ => String::new(),
@@ -1095,7 +1133,18 @@ fn parse_param(
     } else if param_list[3].starts_with("byte-data") {
         ParamType::ArrayByte
     } else if param_list[3].starts_with("array of int") {
-        ParamType::ArrayInt
+        // Arrays of enums are typed as arrays of int, e.g. "Array of VipsBlendMode to join with"
+        match description
+            .strip_prefix("Array of Vips")
+            .and_then(|rest| {
+                rest.split_whitespace()
+                    .next()
+            }) {
+            Some(enum_name) => ParamType::ArrayEnum {
+                name: enum_name.to_string(),
+            },
+            None => ParamType::ArrayInt,
+        }
     } else if param_list[3].starts_with("array of double") {
         ParamType::ArrayDouble
     } else if param_list[3].starts_with("array of images") {
@@ -0,0 +1,410 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    voption::{Setter, VOption},
+    Rect, Result, VipsImage, VipsInterpolate,
+};
+
+/// A 2D affine transform mapping `(x, y)` to `(a * x + b * y + tx, c * x + d * y + ty)`,
+/// in image coordinates with the y axis pointing down.
+///
+/// ```no_run
+/// use rs_vips::{Affine2D, OutputArea, VipsImage, VipsInterpolate};
+///
+/// let image = VipsImage::new_from_file("input.jpg").unwrap();
+/// let transform = Affine2D::rotate(30.0)
+///     .then(&Affine2D::scale(0.5, 0.5));
+/// let (rotated, bounds) = image
+///     .transform(
+///         &transform,
+///         &VipsInterpolate::new_from_bilinear_static(),
+///         OutputArea::Bounds,
+///     )
+///     .unwrap();
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine2D {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub tx: f64,
+    pub ty: f64,
+}
+
+impl Default for Affine2D {
+    fn default() -> Self {
+        Affine2D::identity()
+    }
+}
+
+impl Affine2D {
+    pub fn new(a: f64, b: f64, c: f64, d: f64, tx: f64, ty: f64) -> Affine2D {
+        Affine2D {
+            a,
+            b,
+            c,
+            d,
+            tx,
+            ty,
+        }
+    }
+
+    pub fn identity() -> Affine2D {
+        Affine2D::new(
+            1.0, 0.0, 0.0, 1.0, 0.0, 0.0,
+        )
+    }
+
+    pub fn translate(tx: f64, ty: f64) -> Affine2D {
+        Affine2D::new(
+            1.0, 0.0, 0.0, 1.0, tx, ty,
+        )
+    }
+
+    /// A rotation about the origin, clockwise on screen, by `angle` degrees
+    pub fn rotate(angle: f64) -> Affine2D {
+        let (sin, cos) = angle
+            .to_radians()
+            .sin_cos();
+        Affine2D::new(
+            cos, -sin, sin, cos, 0.0, 0.0,
+        )
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Affine2D {
+        Affine2D::new(
+            sx, 0.0, 0.0, sy, 0.0, 0.0,
+        )
+    }
+
+    /// A shear moving x by `shx * y` and y by `shy * x`
+    pub fn shear(shx: f64, shy: f64) -> Affine2D {
+        Affine2D::new(
+            1.0, shx, shy, 1.0, 0.0, 0.0,
+        )
+    }
+
+    /// Returns the transform which applies this transform and then `next`.
+    pub fn then(&self, next: &Affine2D) -> Affine2D {
+        Affine2D::new(
+            next.a * self.a + next.b * self.c,
+            next.a * self.b + next.b * self.d,
+            next.c * self.a + next.d * self.c,
+            next.c * self.b + next.d * self.d,
+            next.a * self.tx + next.b * self.ty + next.tx,
+            next.c * self.tx + next.d * self.ty + next.ty,
+        )
+    }
+
+    /// Returns the inverse transform, or `None` if the transform is singular.
+    pub fn invert(&self) -> Option<Affine2D> {
+        let det = self.a * self.d - self.b * self.c;
+        // Compare with the size of the entries, so that transforms of any scale can be inverted
+        let magnitude = self
+            .matrix()
+            .iter()
+            .fold(
+                0.0,
+                |max: f64, value| max.max(value.abs()),
+            );
+        if !det.is_finite() || det.abs() <= f64::EPSILON * magnitude * magnitude {
+            return None;
+        }
+        let (a, b, c, d) = (
+            self.d / det,
+            -self.b / det,
+            -self.c / det,
+            self.a / det,
+        );
+        Some(
+            Affine2D::new(
+                a,
+                b,
+                c,
+                d,
+                -(a * self.tx + b * self.ty),
+                -(c * self.tx + d * self.ty),
+            ),
+        )
+    }
+
+    /// The linear part in the order `affine` takes it
+    pub fn matrix(&self) -> [f64; 4] {
+        [self.a, self.b, self.c, self.d]
+    }
+
+    /// Maps a point.
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.b * y + self.tx,
+            self.c * x + self.d * y + self.ty,
+        )
+    }
+
+    /// The smallest whole-pixel rectangle enclosing the transformed rectangle
+    pub fn transform_rect(&self, rect: &Rect) -> Rect {
+        let corners = [
+            (
+                rect.left,
+                rect.top,
+            ),
+            (
+                rect.right(),
+                rect.top,
+            ),
+            (
+                rect.left,
+                rect.bottom(),
+            ),
+            (
+                rect.right(),
+                rect.bottom(),
+            ),
+        ]
+        .map(|(x, y)| {
+            self.apply(
+                x as f64,
+                y as f64,
+            )
+        });
+        let left = corners
+            .iter()
+            .map(|p| p.0)
+            .fold(
+                f64::INFINITY,
+                f64::min,
+            )
+            .floor();
+        let top = corners
+            .iter()
+            .map(|p| p.1)
+            .fold(
+                f64::INFINITY,
+                f64::min,
+            )
+            .floor();
+        let right = corners
+            .iter()
+            .map(|p| p.0)
+            .fold(
+                f64::NEG_INFINITY,
+                f64::max,
+            )
+            .ceil();
+        let bottom = corners
+            .iter()
+            .map(|p| p.1)
+            .fold(
+                f64::NEG_INFINITY,
+                f64::max,
+            )
+            .ceil();
+        Rect::new(
+            left as i32,
+            top as i32,
+            (right - left) as i32,
+            (bottom - top) as i32,
+        )
+    }
+}
+
+/// The area of the transformed plane which `VipsImage::transform` renders
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OutputArea {
+    /// The bounding box of the transformed image, so nothing is clipped
+    Bounds,
+    /// The area of the input image, so the output has the size of the input
+    Input,
+    /// An area in transformed coordinates
+    Rect(Rect),
+}
+
+impl VipsImage {
+    /// Transforms the image with `affine`, rendering `output_area` of the transformed plane.
+    ///
+    /// Returns the output image and the bounding box of the transformed input in output image coordinates,
+    /// which may extend beyond the output for `OutputArea::Input` and `OutputArea::Rect`.
+    pub fn transform(
+        &self,
+        transform: &Affine2D,
+        interpolate: &VipsInterpolate,
+        output_area: OutputArea,
+    ) -> Result<(
+        VipsImage,
+        Rect,
+    )> {
+        self.transform_with_opts(
+            transform,
+            interpolate,
+            output_area,
+            VOption::new(),
+        )
+    }
+
+    /// Transforms the image with `affine` options such as `background`, `extend` and `premultiplied`.
+    pub fn transform_with_opts(
+        &self,
+        transform: &Affine2D,
+        interpolate: &VipsInterpolate,
+        output_area: OutputArea,
+        option: VOption,
+    ) -> Result<(
+        VipsImage,
+        Rect,
+    )> {
+        let bounds = transform.transform_rect(&Rect::of_image(self));
+        let area = match output_area {
+            OutputArea::Bounds => bounds,
+            OutputArea::Input => Rect::of_image(self),
+            OutputArea::Rect(rect) => rect,
+        };
+        let oarea = [area.left, area.top, area.width, area.height];
+        let out = self.affine_with_opts(
+            &transform.matrix(),
+            option
+                .set(
+                    "interpolate",
+                    interpolate,
+                )
+                .set(
+                    "odx",
+                    transform.tx,
+                )
+                .set(
+                    "ody",
+                    transform.ty,
+                )
+                .set(
+                    "oarea",
+                    oarea.as_slice(),
+                ),
+        )?;
+        Ok((
+            out,
+            bounds.translate(
+                -area.left,
+                -area.top,
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(left: (f64, f64), right: (f64, f64)) {
+        assert!(
+            (left.0 - right.0).abs() < 1e-9 && (left.1 - right.1).abs() < 1e-9,
+            "{:?} != {:?}",
+            left,
+            right
+        );
+    }
+
+    #[test]
+    fn apply() {
+        let transform = Affine2D::new(
+            2.0, 1.0, -1.0, 3.0, 10.0, 20.0,
+        );
+        assert_close(
+            transform.apply(1.0, 2.0),
+            (14.0, 25.0),
+        );
+        assert_close(
+            Affine2D::identity().apply(3.0, -4.0),
+            (3.0, -4.0),
+        );
+        // Clockwise on screen, with y pointing down
+        assert_close(
+            Affine2D::rotate(90.0).apply(1.0, 0.0),
+            (0.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn compose() {
+        let first = Affine2D::translate(5.0, -3.0);
+        let second = Affine2D::scale(2.0, 4.0);
+        let both = first.then(&second);
+        let (x, y) = first.apply(1.5, 2.5);
+        assert_close(
+            both.apply(1.5, 2.5),
+            second.apply(x, y),
+        );
+        // Scaling first gives a different transform
+        assert_close(
+            second
+                .then(&first)
+                .apply(1.0, 1.0),
+            (7.0, 1.0),
+        );
+        assert_close(
+            both.apply(1.0, 1.0),
+            (12.0, -8.0),
+        );
+    }
+
+    #[test]
+    fn invert() {
+        let transform = Affine2D::rotate(30.0)
+            .then(&Affine2D::scale(2.0, 0.5))
+            .then(&Affine2D::translate(7.0, -2.0));
+        let inverse = transform
+            .invert()
+            .unwrap();
+        let (x, y) = transform.apply(3.0, 4.0);
+        assert_close(
+            inverse.apply(x, y),
+            (3.0, 4.0),
+        );
+        let identity = transform.then(&inverse);
+        assert_close(
+            identity.apply(0.0, 0.0),
+            (0.0, 0.0),
+        );
+        assert_close(
+            identity.apply(1.0, 1.0),
+            (1.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn invert_small_and_large_scales() {
+        for scale in [1e-6, 1e-12, 1e9] {
+            let transform = Affine2D::scale(
+                scale, scale,
+            );
+            let inverse = transform
+                .invert()
+                .unwrap();
+            assert_close(
+                inverse.apply(
+                    scale,
+                    2.0 * scale,
+                ),
+                (1.0, 2.0),
+            );
+        }
+    }
+
+    #[test]
+    fn invert_singular() {
+        assert_eq!(
+            Affine2D::scale(0.0, 1.0).invert(),
+            None
+        );
+        assert_eq!(
+            Affine2D::new(1.0, 2.0, 2.0, 4.0, 0.0, 0.0).invert(),
+            None
+        );
+        assert_eq!(
+            Affine2D::new(1e-9, 2e-9, 2e-9, 4e-9, 1.0, 1.0).invert(),
+            None
+        );
+        assert_eq!(
+            Affine2D::scale(0.0, 0.0).invert(),
+            None
+        );
+    }
+}
@@ -0,0 +1,280 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    error::Error,
+    voption::{Setter, VOption},
+    Result, VipsImage,
+};
+
+/// Frame delay used when an image has no `delay` metadata, in milliseconds
+const DEFAULT_DELAY: i32 = 100;
+
+/// An animated image. libvips loads the frames of GIF and WebP animations stacked vertically
+/// in one tall image, with `page-height`, `delay` and `loop` metadata.
+#[derive(Clone)]
+pub struct Animation {
+    image: VipsImage,
+}
+
+impl Animation {
+    /// Wraps an image loaded with all its pages, e.g. with `n=-1`.
+    pub fn new(image: VipsImage) -> Animation {
+        Animation {
+            image,
+        }
+    }
+
+    /// Stacks the frames into one image with `page-height`, `n-pages`, `delay` and `loop` set, ready for `gifsave` or `webpsave`.
+    /// All frames must have the same size. `delays` are in milliseconds, one per frame.
+    pub fn assemble(frames: &[VipsImage], delays: &[i32]) -> Result<Animation> {
+        if delays.len() != frames.len() {
+            return Err(
+                Error::OperationError(format!(
+                    "Expected {} delays, got {}",
+                    frames.len(),
+                    delays.len()
+                )),
+            );
+        }
+        let mut image = stack(frames)?;
+        image.set_array_int(
+            "delay",
+            delays,
+        )?;
+        // The older centisecond delay of the first frame would disagree with `delay`
+        image.remove("gif-delay")?;
+        if image
+            .get_int("loop")
+            .is_err()
+        {
+            image.set_int("loop", 0)?;
+        }
+        Ok(
+            Animation {
+                image,
+            },
+        )
+    }
+
+    /// The stacked image
+    pub fn image(&self) -> &VipsImage {
+        &self.image
+    }
+
+    pub fn into_image(self) -> VipsImage {
+        self.image
+    }
+
+    pub fn width(&self) -> i32 {
+        self.image
+            .get_width()
+    }
+
+    /// Height of a frame
+    pub fn frame_height(&self) -> i32 {
+        self.image
+            .get_page_height()
+    }
+
+    pub fn n_frames(&self) -> i32 {
+        self.image
+            .get_height()
+            / self.frame_height()
+    }
+
+    /// Returns the frame at the index, cropped from the stacked image.
+    pub fn frame(&self, index: i32) -> Result<VipsImage> {
+        if index < 0 || index >= self.n_frames() {
+            return Err(
+                Error::OperationError(format!(
+                    "Frame {} out of range",
+                    index
+                )),
+            );
+        }
+        self.image
+            .extract_area(
+                0,
+                index * self.frame_height(),
+                self.width(),
+                self.frame_height(),
+            )
+    }
+
+    /// Returns every frame, cropped from the stacked image.
+    pub fn frames(&self) -> Result<Vec<VipsImage>> {
+        (0..self.n_frames())
+            .map(|index| self.frame(index))
+            .collect()
+    }
+
+    /// Applies `f` to each frame, e.g. `resize`, `crop` or `rotate`, and reassembles the animation
+    /// keeping the delays and loop count. Every frame must come out of `f` with the same size.
+    pub fn map<F: FnMut(&VipsImage) -> Result<VipsImage>>(&self, mut f: F) -> Result<Animation> {
+        let frames = self
+            .frames()?
+            .iter()
+            .map(&mut f)
+            .collect::<Result<Vec<VipsImage>>>()?;
+        let mut animation = Animation::assemble(
+            &frames,
+            &self.delays(),
+        )?;
+        if animation.loop_count() != self.loop_count() {
+            animation.set_loop_count(self.loop_count())?;
+        }
+        Ok(animation)
+    }
+
+    /// Delay of each frame in milliseconds, from `delay` or else the older `gif-delay` in centiseconds.
+    pub fn delays(&self) -> Vec<i32> {
+        let n_frames = self.n_frames() as usize;
+        let mut delays = self
+            .image
+            .get_array_int("delay")
+            .unwrap_or_else(|_| {
+                let delay = self
+                    .image
+                    .get_int("gif-delay")
+                    .map(|centiseconds| centiseconds * 10)
+                    .unwrap_or(DEFAULT_DELAY);
+                vec![delay; n_frames]
+            });
+        let last = delays
+            .last()
+            .copied()
+            .unwrap_or(DEFAULT_DELAY);
+        delays.resize(
+            n_frames,
+            last,
+        );
+        delays
+    }
+
+    /// Sets the delay of each frame in milliseconds.
+    pub fn set_delays(&mut self, delays: &[i32]) -> Result<()> {
+        if delays.len() != self.n_frames() as usize {
+            return Err(
+                Error::OperationError(format!(
+                    "Expected {} delays, got {}",
+                    self.n_frames(),
+                    delays.len()
+                )),
+            );
+        }
+        self.image = self
+            .image
+            .copy()?;
+        self.image
+            .set_array_int(
+                "delay",
+                delays,
+            )
+    }
+
+    /// Number of times the animation repeats, 0 meaning forever
+    pub fn loop_count(&self) -> i32 {
+        self.image
+            .get_int("loop")
+            .unwrap_or(0)
+    }
+
+    pub fn set_loop_count(&mut self, loop_count: i32) -> Result<()> {
+        self.image = self
+            .image
+            .copy()?;
+        self.image
+            .set_int(
+                "loop",
+                loop_count,
+            )
+    }
+}
+
+impl VipsImage {
+    /// Applies `f` to each page of a multi-page image and stacks the results again with `page-height` and `n-pages` updated.
+    /// `delay` and `loop` are set only when the image has them.
+    /// Every page must come out of `f` with the same size.
+    ///
+    /// ```no_run
+    /// use rs_vips::VipsImage;
+    ///
+    /// let image = VipsImage::new_from_file("animated.gif[n=-1]").unwrap();
+    /// let thumbnail = image
+    ///     .map_pages(|page| page.resize(0.5))
+    ///     .unwrap();
+    /// thumbnail.gifsave("thumbnail.gif").unwrap();
+    /// ```
+    pub fn map_pages<F: FnMut(&VipsImage) -> Result<VipsImage>>(&self, f: F) -> Result<VipsImage> {
+        let source = Animation::new(self.clone());
+        let frames = source
+            .frames()?
+            .iter()
+            .map(f)
+            .collect::<Result<Vec<VipsImage>>>()?;
+        let mut image = stack(&frames)?;
+        // Pages carry the metadata of the source, but `f` may have dropped or changed it
+        if self
+            .get_array_int("delay")
+            .is_ok()
+        {
+            image.set_array_int(
+                "delay",
+                &source.delays(),
+            )?;
+        } else {
+            image.remove("delay")?;
+        }
+        match self.get_int("loop") {
+            Ok(loop_count) => image.set_int(
+                "loop",
+                loop_count,
+            )?,
+            Err(_) => {
+                image.remove("loop")?;
+            }
+        }
+        Ok(image)
+    }
+}
+
+/// Stacks frames of the same size vertically, with `page-height` and `n-pages` describing the joined image.
+fn stack(frames: &[VipsImage]) -> Result<VipsImage> {
+    let Some(first) = frames.first() else {
+        return Err(Error::OperationError("Animation has no frames".to_string()));
+    };
+    let (width, height) = (
+        first.get_width(),
+        first.get_height(),
+    );
+    if frames
+        .iter()
+        .any(|frame| frame.get_width() != width || frame.get_height() != height)
+    {
+        return Err(Error::OperationError("Animation frames must have the same size".to_string()));
+    }
+
+    let joined = VipsImage::arrayjoin_with_opts(
+        frames,
+        VOption::new().set(
+            "across",
+            1,
+        ),
+    )?;
+    // Operation outputs may be shared through the cache, so set metadata on a copy
+    let mut image = joined.copy()?;
+    image.set_int(
+        "page-height",
+        height,
+    )?;
+    image.set_int(
+        "n-pages",
+        frames.len() as i32,
+    )?;
+    Ok(image)
+}
+
+impl From<VipsImage> for Animation {
+    fn from(image: VipsImage) -> Self {
+        Animation::new(image)
+    }
+}
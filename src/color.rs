@@ -0,0 +1,380 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    bindings,
+    enums::{BandFormat, Interpretation},
+    voption::{Setter, VOption},
+    Result, VipsImage,
+};
+
+/// A colour for the `ink`, `background` and fill parameters of operations.
+///
+/// Operations take these as `&[f64]` in the pixel values of the image they apply to.
+/// A colour can be set as an option, e.g. `background`, and is converted for the input image of the operation.
+/// The `draw_*_color` methods take one as ink, and [`Color::to_ink`] converts a colour for a given image.
+///
+/// ```no_run
+/// use rs_vips::{voption::{Setter, VOption}, Color, VipsImage};
+///
+/// let mut image = VipsImage::new_from_file("input.jpg").unwrap();
+/// image
+///     .draw_circle_color(&Color::srgb8(255, 0, 0), 100, 100, 50)
+///     .unwrap();
+/// let framed = image
+///     .embed_with_opts(
+///         10,
+///         10,
+///         image.get_width() + 20,
+///         image.get_height() + 20,
+///         VOption::new()
+///             .set("extend", rs_vips::enums::Extend::Background as i32)
+///             .set("background", &Color::grey(128)),
+///     )
+///     .unwrap();
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Color {
+    /// 8-bit sRGB
+    Srgb8 {
+        r: u8,
+        g: u8,
+        b: u8,
+        alpha: Option<f64>,
+    },
+    /// 16-bit sRGB
+    Srgb16 {
+        r: u16,
+        g: u16,
+        b: u16,
+        alpha: Option<f64>,
+    },
+    /// CIELAB with L from 0 to 100
+    Lab {
+        l: f64,
+        a: f64,
+        b: f64,
+        alpha: Option<f64>,
+    },
+    /// 8-bit CMYK, 255 being full ink
+    Cmyk {
+        c: u8,
+        m: u8,
+        y: u8,
+        k: u8,
+        alpha: Option<f64>,
+    },
+    /// 8-bit grey
+    Grey {
+        value: u8,
+        alpha: Option<f64>,
+    },
+}
+
+impl Color {
+    pub fn srgb8(r: u8, g: u8, b: u8) -> Color {
+        Color::Srgb8 {
+            r,
+            g,
+            b,
+            alpha: None,
+        }
+    }
+
+    pub fn srgb16(r: u16, g: u16, b: u16) -> Color {
+        Color::Srgb16 {
+            r,
+            g,
+            b,
+            alpha: None,
+        }
+    }
+
+    pub fn lab(l: f64, a: f64, b: f64) -> Color {
+        Color::Lab {
+            l,
+            a,
+            b,
+            alpha: None,
+        }
+    }
+
+    pub fn cmyk(c: u8, m: u8, y: u8, k: u8) -> Color {
+        Color::Cmyk {
+            c,
+            m,
+            y,
+            k,
+            alpha: None,
+        }
+    }
+
+    pub fn grey(value: u8) -> Color {
+        Color::Grey {
+            value,
+            alpha: None,
+        }
+    }
+
+    /// Sets the opacity, from 0.0 for transparent to 1.0 for opaque.
+    pub fn with_alpha(mut self, opacity: f64) -> Color {
+        let opacity = Some(opacity.clamp(0.0, 1.0));
+        match &mut self {
+            Color::Srgb8 {
+                alpha,
+                ..
+            }
+            | Color::Srgb16 {
+                alpha,
+                ..
+            }
+            | Color::Lab {
+                alpha,
+                ..
+            }
+            | Color::Cmyk {
+                alpha,
+                ..
+            }
+            | Color::Grey {
+                alpha,
+                ..
+            } => *alpha = opacity,
+        }
+        self
+    }
+
+    /// The opacity, from 0.0 to 1.0, if set
+    pub fn alpha(&self) -> Option<f64> {
+        match *self {
+            Color::Srgb8 {
+                alpha,
+                ..
+            }
+            | Color::Srgb16 {
+                alpha,
+                ..
+            }
+            | Color::Lab {
+                alpha,
+                ..
+            }
+            | Color::Cmyk {
+                alpha,
+                ..
+            }
+            | Color::Grey {
+                alpha,
+                ..
+            } => alpha,
+        }
+    }
+
+    /// The colour bands with the interpretation and format they are expressed in
+    fn components(
+        &self,
+    ) -> (
+        Vec<f64>,
+        Interpretation,
+        BandFormat,
+    ) {
+        match *self {
+            Color::Srgb8 {
+                r,
+                g,
+                b,
+                ..
+            } => (
+                vec![r as f64, g as f64, b as f64],
+                Interpretation::Srgb,
+                BandFormat::Uchar,
+            ),
+            Color::Srgb16 {
+                r,
+                g,
+                b,
+                ..
+            } => (
+                vec![r as f64, g as f64, b as f64],
+                Interpretation::Rgb16,
+                BandFormat::Ushort,
+            ),
+            Color::Lab {
+                l,
+                a,
+                b,
+                ..
+            } => (
+                vec![l, a, b],
+                Interpretation::Lab,
+                BandFormat::Float,
+            ),
+            Color::Cmyk {
+                c,
+                m,
+                y,
+                k,
+                ..
+            } => (
+                vec![c as f64, m as f64, y as f64, k as f64],
+                Interpretation::Cmyk,
+                BandFormat::Uchar,
+            ),
+            Color::Grey {
+                value,
+                ..
+            } => (
+                vec![value as f64],
+                Interpretation::BW,
+                BandFormat::Uchar,
+            ),
+        }
+    }
+
+    /// Converts the colour bands to the interpretation with `colourspace`.
+    pub(crate) fn convert(&self, target: Interpretation) -> Result<Vec<f64>> {
+        let (values, interpretation, format) = self.components();
+        if interpretation == target {
+            return Ok(values);
+        }
+        VipsImage::black_with_opts(
+            1,
+            1,
+            VOption::new().set(
+                "bands",
+                values.len() as i32,
+            ),
+        )?
+        .linear(
+            &[1.0],
+            &values,
+        )?
+        .cast(format)?
+        .copy_with_opts(
+            VOption::new().set(
+                "interpretation",
+                interpretation as i32,
+            ),
+        )?
+        .colourspace(target)?
+        .getpoint(0, 0)
+    }
+
+    /// Converts the colour to the pixel values of `image`, for its interpretation and number of bands.
+    ///
+    /// The colour is converted with `colourspace` when the image is in another colour space. If the image has
+    /// an alpha band, the opacity is appended scaled to the alpha range of the image, defaulting to opaque.
+    pub fn to_ink(&self, image: &VipsImage) -> Result<Vec<f64>> {
+        let has_alpha = image.hasalpha();
+        let bands = image.get_bands() as usize;
+        let colour_bands = if has_alpha { bands - 1 } else { bands };
+        let target = match image.get_interpretation()? {
+            Interpretation::Multiband
+            | Interpretation::Histogram
+            | Interpretation::Fourier
+            | Interpretation::Matrix
+            | Interpretation::Error => {
+                if colour_bands < 3 {
+                    Interpretation::BW
+                } else {
+                    Interpretation::Srgb
+                }
+            }
+            // LabQ is coded, draw operations take Lab values for it
+            Interpretation::Labq => Interpretation::Lab,
+            interpretation => interpretation,
+        };
+
+        let mut ink = self.convert(target)?;
+
+        // Pad with the last band or drop extra bands for images with an unusual band count
+        let last = ink
+            .last()
+            .copied()
+            .unwrap_or_default();
+        ink.resize(
+            colour_bands,
+            last,
+        );
+        if has_alpha {
+            let max_alpha = unsafe { bindings::vips_interpretation_max_alpha(target as i32) };
+            ink.push(
+                self.alpha()
+                    .unwrap_or(1.0)
+                    * max_alpha,
+            );
+        }
+        Ok(ink)
+    }
+}
+
+impl VipsImage {
+    /// Draws a circle in the colour, as `draw_circle`.
+    pub fn draw_circle_color(
+        &mut self,
+        color: &Color,
+        cx: i32,
+        cy: i32,
+        radius: i32,
+    ) -> Result<()> {
+        let ink = color.to_ink(self)?;
+        self.draw_circle(
+            &ink,
+            cx,
+            cy,
+            radius,
+        )
+    }
+
+    /// Flood-fills in the colour from the point, as `draw_flood`.
+    pub fn draw_flood_color(&mut self, color: &Color, x: i32, y: i32) -> Result<()> {
+        let ink = color.to_ink(self)?;
+        self.draw_flood(&ink, x, y)
+    }
+
+    /// Draws a line in the colour, as `draw_line`.
+    pub fn draw_line_color(
+        &mut self,
+        color: &Color,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+    ) -> Result<()> {
+        let ink = color.to_ink(self)?;
+        self.draw_line(
+            &ink, x1, y1, x2, y2,
+        )
+    }
+
+    /// Draws the colour through a mask, as `draw_mask`.
+    pub fn draw_mask_color(
+        &mut self,
+        color: &Color,
+        mask: &VipsImage,
+        x: i32,
+        y: i32,
+    ) -> Result<()> {
+        let ink = color.to_ink(self)?;
+        self.draw_mask(
+            &ink, mask, x, y,
+        )
+    }
+
+    /// Draws a rectangle in the colour, as `draw_rect`.
+    pub fn draw_rect_color(
+        &mut self,
+        color: &Color,
+        left: i32,
+        top: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<()> {
+        let ink = color.to_ink(self)?;
+        self.draw_rect(
+            &ink,
+            left,
+            top,
+            width,
+            height,
+        )
+    }
+}
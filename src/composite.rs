@@ -0,0 +1,107 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    enums::BlendMode,
+    voption::{Setter, VOption},
+    Result, VipsImage,
+};
+
+/// An image composited over a base image with a blend mode, at a position relative to the base
+#[derive(Clone)]
+pub struct Layer {
+    pub image: VipsImage,
+    pub mode: BlendMode,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Layer {
+    /// A layer blended with `BlendMode::Over` at the top-left corner
+    pub fn new(image: VipsImage) -> Layer {
+        Layer {
+            image,
+            mode: BlendMode::Over,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    pub fn mode(mut self, mode: BlendMode) -> Layer {
+        self.mode = mode;
+        self
+    }
+
+    pub fn position(mut self, x: i32, y: i32) -> Layer {
+        self.x = x;
+        self.y = y;
+        self
+    }
+}
+
+impl VipsImage {
+    /// Composites the layers over this image, from bottom to top.
+    ///
+    /// ```no_run
+    /// use rs_vips::{enums::BlendMode, Layer, VipsImage};
+    ///
+    /// let base = VipsImage::new_from_file("base.png").unwrap();
+    /// let logo = VipsImage::new_from_file("logo.png").unwrap();
+    /// let shadow = VipsImage::new_from_file("shadow.png").unwrap();
+    /// let out = base
+    ///     .composite_layers(&[
+    ///         Layer::new(shadow).mode(BlendMode::Multiply).position(12, 12),
+    ///         Layer::new(logo).position(10, 10),
+    ///     ])
+    ///     .unwrap();
+    /// ```
+    pub fn composite_layers(&self, layers: &[Layer]) -> Result<VipsImage> {
+        self.composite_layers_with_opts(
+            layers,
+            VOption::new(),
+        )
+    }
+
+    /// Composites the layers over this image, from bottom to top, with `composite` options
+    /// such as `compositing_space` and `premultiplied`.
+    pub fn composite_layers_with_opts(
+        &self,
+        layers: &[Layer],
+        option: VOption,
+    ) -> Result<VipsImage> {
+        let images: Vec<VipsImage> = std::iter::once(self.clone())
+            .chain(
+                layers
+                    .iter()
+                    .map(|layer| {
+                        layer
+                            .image
+                            .clone()
+                    }),
+            )
+            .collect();
+        let modes: Vec<BlendMode> = layers
+            .iter()
+            .map(|layer| layer.mode)
+            .collect();
+        let x: Vec<i32> = layers
+            .iter()
+            .map(|layer| layer.x)
+            .collect();
+        let y: Vec<i32> = layers
+            .iter()
+            .map(|layer| layer.y)
+            .collect();
+        VipsImage::composite_with_opts(
+            &images,
+            &modes,
+            option
+                .set(
+                    "x",
+                    x.as_slice(),
+                )
+                .set(
+                    "y",
+                    y.as_slice(),
+                ),
+        )
+    }
+}
@@ -0,0 +1,236 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{bindings, error::Error, utils::new_c_string, Result};
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicU8, AtomicUsize, Ordering},
+        Condvar, Mutex, MutexGuard,
+    },
+};
+
+const UNINITIALIZED: u8 = 0;
+const RUNNING: u8 = 1;
+const SHUT_DOWN: u8 = 2;
+
+/// Lifecycle phase, read without locking on every call into libvips
+static PHASE: AtomicU8 = AtomicU8::new(UNINITIALIZED);
+/// Number of threads currently inside libvips through a `CallGuard`
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+/// Signalled when the last `CallGuard` of a thread is dropped after shutdown has begun
+static DRAINED: Condvar = Condvar::new();
+
+thread_local! {
+    /// Nesting depth of `CallGuard`s on this thread, so that nested calls count once
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Lifecycle bookkeeping, changed only under the lock so that starting up and shutting down cannot interleave
+struct State {
+    /// Number of live `VipsContext`s
+    contexts: usize,
+    /// Set by `Vips::init`, which keeps libvips initialized until `Vips::shutdown`
+    pinned: bool,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    contexts: 0,
+    pinned: false,
+});
+
+fn lock() -> MutexGuard<'static, State> {
+    STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Initializes libvips unless it is already initialized. A failed `vips_init` can be retried.
+fn start(name: &str) -> Result<()> {
+    match PHASE.load(Ordering::SeqCst) {
+        SHUT_DOWN => {
+            Err(Error::InitializationError("libvips has already been shut down".to_string()))
+        }
+        RUNNING => Ok(()),
+        _ => {
+            let c_name = new_c_string(name)?;
+            if unsafe { bindings::vips_init(c_name.as_ptr()) } != 0 {
+                return Err(Error::InitializationError("Failed to init libvips".to_string()));
+            }
+            PHASE.store(
+                RUNNING,
+                Ordering::SeqCst,
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Refuses new calls, waits for the calls in flight on other threads to return and shuts libvips down.
+fn stop(state: MutexGuard<'static, State>) {
+    if PHASE.swap(
+        SHUT_DOWN,
+        Ordering::SeqCst,
+    ) != RUNNING
+    {
+        return;
+    }
+    // A call in flight on this thread cannot return while we wait, so it is not waited for
+    let own = if DEPTH.get() > 0 { 1 } else { 0 };
+    let _state = DRAINED
+        .wait_while(
+            state,
+            |_| IN_FLIGHT.load(Ordering::SeqCst) > own,
+        )
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    unsafe {
+        bindings::vips_shutdown();
+    }
+}
+
+/// Initializes libvips and keeps it initialized until `shutdown`, whatever contexts are dropped.
+pub(crate) fn init(name: &str) -> Result<()> {
+    let mut state = lock();
+    start(name)?;
+    state.pinned = true;
+    Ok(())
+}
+
+/// Shuts libvips down once. Later calls do nothing.
+pub(crate) fn shutdown() {
+    let state = lock();
+    if PHASE.load(Ordering::SeqCst) == UNINITIALIZED {
+        PHASE.store(
+            SHUT_DOWN,
+            Ordering::SeqCst,
+        );
+        return;
+    }
+    stop(state);
+}
+
+pub(crate) fn is_initialized() -> bool {
+    PHASE.load(Ordering::SeqCst) == RUNNING
+}
+
+/// Keeps libvips from being shut down while it is alive. Created by [`enter`].
+pub(crate) struct CallGuard {
+    _private: (),
+}
+
+impl Drop for CallGuard {
+    fn drop(&mut self) {
+        let depth = DEPTH.get() - 1;
+        DEPTH.set(depth);
+        if depth == 0 {
+            IN_FLIGHT.fetch_sub(
+                1,
+                Ordering::SeqCst,
+            );
+            if PHASE.load(Ordering::SeqCst) == SHUT_DOWN {
+                // Taking the lock orders the notification after the shutting down thread starts waiting
+                let _state = lock();
+                DRAINED.notify_all();
+            }
+        }
+    }
+}
+
+/// Returns a guard which keeps libvips from being shut down until it is dropped,
+/// or an error unless libvips is initialized and not shut down yet.
+///
+/// Hold the guard for the whole call into libvips. Guards nest on a thread.
+#[must_use = "libvips can be shut down as soon as the guard is dropped"]
+pub(crate) fn enter() -> Result<CallGuard> {
+    let depth = DEPTH.get();
+    if depth > 0 {
+        DEPTH.set(depth + 1);
+        return Ok(
+            CallGuard {
+                _private: (),
+            },
+        );
+    }
+
+    // Announce the call before checking the phase, so that shutdown either sees it or it sees shutdown
+    IN_FLIGHT.fetch_add(
+        1,
+        Ordering::SeqCst,
+    );
+    match PHASE.load(Ordering::SeqCst) {
+        RUNNING => {
+            DEPTH.set(1);
+            Ok(
+                CallGuard {
+                    _private: (),
+                },
+            )
+        }
+        phase => {
+            IN_FLIGHT.fetch_sub(
+                1,
+                Ordering::SeqCst,
+            );
+            if phase == SHUT_DOWN {
+                let _state = lock();
+                DRAINED.notify_all();
+                Err(Error::InitializationError("libvips has already been shut down".to_string()))
+            } else {
+                Err(Error::InitializationError("libvips is not initialized".to_string()))
+            }
+        }
+    }
+}
+
+/// Unrefs a libvips object unless libvips has been shut down, in which case the object is leaked.
+pub(crate) fn unref<T>(object: *mut T) {
+    if object.is_null() {
+        return;
+    }
+    if let Ok(_guard) = enter() {
+        unsafe {
+            bindings::g_object_unref(object as *mut std::ffi::c_void);
+        }
+    }
+}
+
+/// Keeps libvips initialized while it is alive. Create one by [`crate::Vips::init_context()`].
+///
+/// Any number of contexts can be created from any thread. libvips is initialized only once.
+///
+/// Dropping a context frees the thread-private data of the dropping thread by `vips_thread_shutdown`.
+/// Dropping the last one waits for calls running on other threads and shuts libvips down by `vips_shutdown`,
+/// after which libvips cannot be initialized again, unless [`crate::Vips::init()`] has been called,
+/// which keeps libvips initialized until [`crate::Vips::shutdown()`].
+///
+/// Images and other objects dropped after shutdown are leaked instead of being unreffed.
+#[must_use = "libvips is shut down as soon as the last VipsContext is dropped"]
+pub struct VipsContext {
+    _private: (),
+}
+
+impl VipsContext {
+    pub(crate) fn new(name: &str) -> Result<VipsContext> {
+        let mut state = lock();
+        start(name)?;
+        state.contexts += 1;
+        Ok(
+            VipsContext {
+                _private: (),
+            },
+        )
+    }
+}
+
+impl Drop for VipsContext {
+    fn drop(&mut self) {
+        let mut state = lock();
+        state.contexts -= 1;
+        if state.contexts == 0 && !state.pinned {
+            stop(state);
+        } else {
+            // Still under the lock, so that no other thread can shut libvips down meanwhile
+            unsafe {
+                bindings::vips_thread_shutdown();
+            }
+        }
+    }
+}
@@ -11,6 +11,8 @@ pub enum Access {
     SequentialUnbuffered = 2,
 }
 
+impl crate::voption::VipsEnum for Access {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Align {
     ///  `Low` -> VIPS_ALIGN_LOW = 0
@@ -21,6 +23,8 @@ pub enum Align {
     High = 2,
 }
 
+impl crate::voption::VipsEnum for Align {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Angle {
     ///  `D0` -> VIPS_ANGLE_D0 = 0
@@ -33,6 +37,8 @@ pub enum Angle {
     D270 = 3,
 }
 
+impl crate::voption::VipsEnum for Angle {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Angle45 {
     ///  `D0` -> VIPS_ANGLE45_D0 = 0
@@ -53,6 +59,8 @@ pub enum Angle45 {
     D315 = 7,
 }
 
+impl crate::voption::VipsEnum for Angle45 {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum BandFormat {
     ///  `Notset` -> VIPS_FORMAT_NOTSET = -1
@@ -79,6 +87,8 @@ pub enum BandFormat {
     Dpcomplex = 9,
 }
 
+impl crate::voption::VipsEnum for BandFormat {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum BlendMode {
     ///  `Clear` -> VIPS_BLEND_MODE_CLEAR = 0
@@ -133,6 +143,8 @@ pub enum BlendMode {
     Exclusion = 24,
 }
 
+impl crate::voption::VipsEnum for BlendMode {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Coding {
     ///  `Error` -> VIPS_CODING_ERROR = -1
@@ -145,6 +157,8 @@ pub enum Coding {
     Rad = 6,
 }
 
+impl crate::voption::VipsEnum for Coding {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Combine {
     ///  `Max` -> VIPS_COMBINE_MAX = 0
@@ -155,6 +169,8 @@ pub enum Combine {
     Min = 2,
 }
 
+impl crate::voption::VipsEnum for Combine {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum CombineMode {
     ///  `Set` -> VIPS_COMBINE_MODE_SET = 0
@@ -163,6 +179,8 @@ pub enum CombineMode {
     Add = 1,
 }
 
+impl crate::voption::VipsEnum for CombineMode {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum CompassDirection {
     ///  `Centre` -> VIPS_COMPASS_DIRECTION_CENTRE = 0
@@ -185,6 +203,8 @@ pub enum CompassDirection {
     NorthWest = 8,
 }
 
+impl crate::voption::VipsEnum for CompassDirection {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Direction {
     ///  `Horizontal` -> VIPS_DIRECTION_HORIZONTAL = 0
@@ -193,6 +213,8 @@ pub enum Direction {
     Vertical = 1,
 }
 
+impl crate::voption::VipsEnum for Direction {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Extend {
     ///  `Black` -> VIPS_EXTEND_BLACK = 0
@@ -209,6 +231,8 @@ pub enum Extend {
     Background = 5,
 }
 
+impl crate::voption::VipsEnum for Extend {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum FailOn {
     ///  `None` -> VIPS_FAIL_ON_NONE = 0
@@ -221,6 +245,8 @@ pub enum FailOn {
     Warning = 3,
 }
 
+impl crate::voption::VipsEnum for FailOn {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignDzContainer {
     ///  `Fs` -> VIPS_FOREIGN_DZ_CONTAINER_FS = 0
@@ -231,6 +257,8 @@ pub enum ForeignDzContainer {
     Szi = 2,
 }
 
+impl crate::voption::VipsEnum for ForeignDzContainer {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignDzDepth {
     ///  `Onepixel` -> VIPS_FOREIGN_DZ_DEPTH_ONEPIXEL = 0
@@ -241,6 +269,8 @@ pub enum ForeignDzDepth {
     One = 2,
 }
 
+impl crate::voption::VipsEnum for ForeignDzDepth {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignDzLayout {
     ///  `Dz` -> VIPS_FOREIGN_DZ_LAYOUT_DZ = 0
@@ -255,6 +285,8 @@ pub enum ForeignDzLayout {
     Iiif3 = 4,
 }
 
+impl crate::voption::VipsEnum for ForeignDzLayout {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignFlags {
     ///  `None` -> VIPS_FOREIGN_NONE = 0
@@ -269,6 +301,8 @@ pub enum ForeignFlags {
     All = 7,
 }
 
+impl crate::voption::VipsEnum for ForeignFlags {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignHeifCompression {
     ///  `Hevc` -> VIPS_FOREIGN_HEIF_COMPRESSION_HEVC = 1
@@ -281,6 +315,8 @@ pub enum ForeignHeifCompression {
     Av1 = 4,
 }
 
+impl crate::voption::VipsEnum for ForeignHeifCompression {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignHeifEncoder {
     ///  `Auto` -> VIPS_FOREIGN_HEIF_ENCODER_AUTO = 0
@@ -295,6 +331,8 @@ pub enum ForeignHeifEncoder {
     X265 = 4,
 }
 
+impl crate::voption::VipsEnum for ForeignHeifEncoder {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignKeep {
     ///  `None` -> VIPS_FOREIGN_KEEP_NONE = 0
@@ -315,6 +353,8 @@ pub enum ForeignKeep {
     All = 63,
 }
 
+impl crate::voption::VipsEnum for ForeignKeep {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignPdfPageBox {
     ///  `Medum` -> VIPS_FOREIGN_PDF_PAGE_BOX_MEDIA = 0
@@ -329,6 +369,8 @@ pub enum ForeignPdfPageBox {
     Art = 4,
 }
 
+impl crate::voption::VipsEnum for ForeignPdfPageBox {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignPngFilter {
     ///  `None` -> VIPS_FOREIGN_PNG_FILTER_NONE = 8
@@ -345,6 +387,8 @@ pub enum ForeignPngFilter {
     All = 248,
 }
 
+impl crate::voption::VipsEnum for ForeignPngFilter {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignPpmFormat {
     ///  `Pbm` -> VIPS_FOREIGN_PPM_FORMAT_PBM = 0
@@ -359,6 +403,8 @@ pub enum ForeignPpmFormat {
     Pnm = 4,
 }
 
+impl crate::voption::VipsEnum for ForeignPpmFormat {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignSubsample {
     ///  `Auto` -> VIPS_FOREIGN_SUBSAMPLE_AUTO = 0
@@ -369,6 +415,8 @@ pub enum ForeignSubsample {
     Off = 2,
 }
 
+impl crate::voption::VipsEnum for ForeignSubsample {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignTiffCompression {
     ///  `None` -> VIPS_FOREIGN_TIFF_COMPRESSION_NONE = 0
@@ -391,6 +439,8 @@ pub enum ForeignTiffCompression {
     Jp2K = 8,
 }
 
+impl crate::voption::VipsEnum for ForeignTiffCompression {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignTiffPredictor {
     ///  `None` -> VIPS_FOREIGN_TIFF_PREDICTOR_NONE = 1
@@ -401,6 +451,8 @@ pub enum ForeignTiffPredictor {
     Float = 3,
 }
 
+impl crate::voption::VipsEnum for ForeignTiffPredictor {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignTiffResunit {
     ///  `Cm` -> VIPS_FOREIGN_TIFF_RESUNIT_CM = 0
@@ -409,6 +461,8 @@ pub enum ForeignTiffResunit {
     Inch = 1,
 }
 
+impl crate::voption::VipsEnum for ForeignTiffResunit {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum ForeignWebpPreset {
     ///  `Default` -> VIPS_FOREIGN_WEBP_PRESET_DEFAULT = 0
@@ -425,6 +479,8 @@ pub enum ForeignWebpPreset {
     Text = 5,
 }
 
+impl crate::voption::VipsEnum for ForeignWebpPreset {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Intent {
     ///  `Perceptual` -> VIPS_INTENT_PERCEPTUAL = 0
@@ -439,6 +495,8 @@ pub enum Intent {
     Auto = 32,
 }
 
+impl crate::voption::VipsEnum for Intent {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Interesting {
     ///  `None` -> VIPS_INTERESTING_NONE = 0
@@ -457,6 +515,8 @@ pub enum Interesting {
     All = 6,
 }
 
+impl crate::voption::VipsEnum for Interesting {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Interpretation {
     ///  `Error` -> VIPS_INTERPRETATION_ERROR = -1
@@ -505,6 +565,8 @@ pub enum Interpretation {
     Oklch = 31,
 }
 
+impl crate::voption::VipsEnum for Interpretation {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Kernel {
     ///  `Nearest` -> VIPS_KERNEL_NEAREST = 0
@@ -525,6 +587,8 @@ pub enum Kernel {
     Mks2021 = 7,
 }
 
+impl crate::voption::VipsEnum for Kernel {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationBoolean {
     ///  `And` -> VIPS_OPERATION_BOOLEAN_AND = 0
@@ -539,6 +603,8 @@ pub enum OperationBoolean {
     Rshift = 4,
 }
 
+impl crate::voption::VipsEnum for OperationBoolean {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationComplex {
     ///  `Polar` -> VIPS_OPERATION_COMPLEX_POLAR = 0
@@ -549,12 +615,16 @@ pub enum OperationComplex {
     Conj = 2,
 }
 
+impl crate::voption::VipsEnum for OperationComplex {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationComplex2 {
     ///  `CrossPhase` -> VIPS_OPERATION_COMPLEX2_CROSS_PHASE = 0
     CrossPhase = 0,
 }
 
+impl crate::voption::VipsEnum for OperationComplex2 {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationComplexget {
     ///  `Real` -> VIPS_OPERATION_COMPLEXGET_REAL = 0
@@ -563,6 +633,8 @@ pub enum OperationComplexget {
     Imag = 1,
 }
 
+impl crate::voption::VipsEnum for OperationComplexget {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationMath {
     ///  `Sin` -> VIPS_OPERATION_MATH_SIN = 0
@@ -599,6 +671,8 @@ pub enum OperationMath {
     Atanh = 15,
 }
 
+impl crate::voption::VipsEnum for OperationMath {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationMath2 {
     ///  `Pow` -> VIPS_OPERATION_MATH2_POW = 0
@@ -609,6 +683,8 @@ pub enum OperationMath2 {
     Atan2 = 2,
 }
 
+impl crate::voption::VipsEnum for OperationMath2 {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationMorphology {
     ///  `Erode` -> VIPS_OPERATION_MORPHOLOGY_ERODE = 0
@@ -617,6 +693,8 @@ pub enum OperationMorphology {
     Dilate = 1,
 }
 
+impl crate::voption::VipsEnum for OperationMorphology {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationRelational {
     ///  `Equal` -> VIPS_OPERATION_RELATIONAL_EQUAL = 0
@@ -633,6 +711,8 @@ pub enum OperationRelational {
     Moreeq = 5,
 }
 
+impl crate::voption::VipsEnum for OperationRelational {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum OperationRound {
     ///  `Rint` -> VIPS_OPERATION_ROUND_RINT = 0
@@ -643,6 +723,8 @@ pub enum OperationRound {
     Floor = 2,
 }
 
+impl crate::voption::VipsEnum for OperationRound {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum PCS {
     ///  `Lab` -> VIPS_PCS_LAB = 0
@@ -651,6 +733,8 @@ pub enum PCS {
     Xyz = 1,
 }
 
+impl crate::voption::VipsEnum for PCS {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Precision {
     ///  `Integer` -> VIPS_PRECISION_INTEGER = 0
@@ -661,6 +745,8 @@ pub enum Precision {
     Approximate = 2,
 }
 
+impl crate::voption::VipsEnum for Precision {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum RegionShrink {
     ///  `Mean` -> VIPS_REGION_SHRINK_MEAN = 0
@@ -677,6 +763,8 @@ pub enum RegionShrink {
     Nearest = 5,
 }
 
+impl crate::voption::VipsEnum for RegionShrink {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum SdfShape {
     ///  `Circle` -> VIPS_SDF_SHAPE_CIRCLE = 0
@@ -689,6 +777,8 @@ pub enum SdfShape {
     Line = 3,
 }
 
+impl crate::voption::VipsEnum for SdfShape {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum Size {
     ///  `Both` -> VIPS_SIZE_BOTH = 0
@@ -701,6 +791,8 @@ pub enum Size {
     Force = 3,
 }
 
+impl crate::voption::VipsEnum for Size {}
+
 #[derive(Copy, Clone, Debug, FromPrimitive, ToPrimitive, PartialEq, PartialOrd)]
 pub enum TextWrap {
     ///  `Word` -> VIPS_TEXT_WRAP_WORD = 0
@@ -712,3 +804,5 @@ pub enum TextWrap {
     ///  `None` -> VIPS_TEXT_WRAP_NONE = 3
     None = 3,
 }
+
+impl crate::voption::VipsEnum for TextWrap {}
@@ -6,6 +6,8 @@ pub enum Error {
     InitializationError(String),
     IOError(String),
     OperationError(String),
+    Blocked(String),
+    TypeMismatch(String),
 }
 
 impl std::fmt::Display for Error {
@@ -28,6 +30,16 @@ impl std::fmt::Display for Error {
                 "vips error: IOError - {}",
                 msg
             ),
+            Error::Blocked(msg) => write!(
+                f,
+                "vips error: Blocked - {}",
+                msg
+            ),
+            Error::TypeMismatch(msg) => write!(
+                f,
+                "vips error: TypeMismatch - {}",
+                msg
+            ),
         }
     }
 }
@@ -48,6 +60,14 @@ impl Error {
                     "{}. {}",
                     msg, detail
                 )),
+                Error::Blocked(msg) => Error::Blocked(format!(
+                    "{}. {}",
+                    msg, detail
+                )),
+                Error::TypeMismatch(msg) => Error::TypeMismatch(format!(
+                    "{}. {}",
+                    msg, detail
+                )),
             }
         } else {
             self
@@ -0,0 +1,1623 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{error::Error, Result, VipsImage};
+
+const EXIF_DATA: &str = "exif-data";
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+const FORMAT_BYTE: u16 = 1;
+const FORMAT_ASCII: u16 = 2;
+const FORMAT_SHORT: u16 = 3;
+const FORMAT_LONG: u16 = 4;
+const FORMAT_RATIONAL: u16 = 5;
+
+const TAG_MAKE: u16 = 0x010F;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_DATETIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_GPS_IFD: u16 = 0x8825;
+const TAG_INTEROP_IFD: u16 = 0xA005;
+const TAG_EXPOSURE_TIME: u16 = 0x829A;
+const TAG_F_NUMBER: u16 = 0x829D;
+const TAG_ISO: u16 = 0x8827;
+const TAG_DATETIME_ORIGINAL: u16 = 0x9003;
+const TAG_GPS_VERSION_ID: u16 = 0x0000;
+const TAG_GPS_LATITUDE_REF: u16 = 0x0001;
+const TAG_GPS_LATITUDE: u16 = 0x0002;
+const TAG_GPS_LONGITUDE_REF: u16 = 0x0003;
+const TAG_GPS_LONGITUDE: u16 = 0x0004;
+const TAG_GPS_ALTITUDE_REF: u16 = 0x0005;
+const TAG_GPS_ALTITUDE: u16 = 0x0006;
+const TAG_THUMBNAIL_OFFSET: u16 = 0x0201;
+const TAG_THUMBNAIL_LENGTH: u16 = 0x0202;
+const TAG_MAKER_NOTE: u16 = 0x927C;
+const TAG_DNG_PRIVATE_DATA: u16 = 0xC634;
+
+/// Tags whose values hold offsets into the TIFF data, so they cannot be moved
+const OFFSET_TAGS: [u16; 2] = [TAG_MAKER_NOTE, TAG_DNG_PRIVATE_DATA];
+
+/// IFD numbers libvips uses in the `exif-ifd<N>-<Tag>` field names
+const IFD0: u8 = 0;
+const IFD_EXIF: u8 = 2;
+const IFD_GPS: u8 = 3;
+
+/// GPS position in decimal degrees. Positive latitude is north and positive longitude is east.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GpsCoordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Metres above sea level
+    pub altitude: Option<f64>,
+}
+
+/// A typed view of the common EXIF tags of an image, parsed from the `exif-ifd*` metadata fields
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Exif {
+    /// Orientation from 1 (top-left) to 8 (left-bottom)
+    pub orientation: Option<u16>,
+    /// Date and time of the last change, as `YYYY:MM:DD HH:MM:SS`
+    pub datetime: Option<String>,
+    /// Date and time when the picture was taken, as `YYYY:MM:DD HH:MM:SS`
+    pub datetime_original: Option<String>,
+    pub make: Option<String>,
+    pub model: Option<String>,
+    /// Exposure time in seconds
+    pub exposure_time: Option<f64>,
+    pub f_number: Option<f64>,
+    pub iso: Option<u16>,
+    pub gps: Option<GpsCoordinates>,
+}
+
+fn field_name(ifd: u8, tag_name: &str) -> String {
+    format!(
+        "exif-ifd{}-{}",
+        ifd, tag_name
+    )
+}
+
+/// Returns the raw value of a libvips EXIF string such as `1 (Top-left, Short, 1 components, 2 bytes)`.
+fn raw_value(field: &str) -> &str {
+    // ASCII values are repeated in the parentheses, e.g. `Canon (Canon, ASCII, 6 components, 6 bytes)`
+    for (pos, _) in field.match_indices(" (") {
+        let raw = &field[..pos];
+        if field[pos + 2..].starts_with(&format!(
+            "{}, ",
+            raw
+        )) {
+            return raw;
+        }
+    }
+    match field.find(" (") {
+        Some(pos) => &field[..pos],
+        None => field.trim_end(),
+    }
+}
+
+fn parse_rational(value: &str) -> Option<f64> {
+    let (numerator, denominator) = value.split_once('/')?;
+    let numerator: f64 = numerator
+        .parse()
+        .ok()?;
+    let denominator: f64 = denominator
+        .parse()
+        .ok()?;
+    if denominator == 0.0 {
+        None
+    } else {
+        Some(numerator / denominator)
+    }
+}
+
+/// Approximates the value by a fraction using continued fractions.
+fn to_rational(value: f64) -> (u32, u32) {
+    const MAX_DENOMINATOR: f64 = 100_000.0;
+    let value = value.abs();
+    let (mut n0, mut d0, mut n1, mut d1) = (
+        0.0, 1.0, 1.0, 0.0,
+    );
+    let mut x = value;
+    loop {
+        let a = x.floor();
+        let (n2, d2) = (
+            a * n1 + n0,
+            a * d1 + d0,
+        );
+        if d2 > MAX_DENOMINATOR || n2 > u32::MAX as f64 {
+            break;
+        }
+        (
+            n0, d0, n1, d1,
+        ) = (
+            n1, d1, n2, d2,
+        );
+        if (x - a).abs() < 1e-9 || (n1 / d1 - value).abs() < 1e-12 {
+            break;
+        }
+        x = 1.0 / (x - a);
+    }
+    if d1 == 0.0 {
+        (
+            value.round() as u32,
+            1,
+        )
+    } else {
+        (
+            n1 as u32,
+            d1 as u32,
+        )
+    }
+}
+
+fn degrees_from_dms(value: &str) -> Option<f64> {
+    let parts: Vec<f64> = value
+        .split_whitespace()
+        .map(parse_rational)
+        .collect::<Option<Vec<f64>>>()?;
+    match parts.as_slice() {
+        [degrees, minutes, seconds] => Some(degrees + minutes / 60.0 + seconds / 3600.0),
+        [degrees, minutes] => Some(degrees + minutes / 60.0),
+        [degrees] => Some(*degrees),
+        _ => None,
+    }
+}
+
+fn dms_from_degrees(value: f64) -> [(u32, u32); 3] {
+    // Round to hundredths of a second first so that 59.995 seconds carry into the minutes
+    let hundredths = (value.abs() * 360_000.0).round() as u64;
+    [
+        (
+            (hundredths / 360_000) as u32,
+            1,
+        ),
+        (
+            (hundredths % 360_000 / 6_000) as u32,
+            1,
+        ),
+        (
+            (hundredths % 6_000) as u32,
+            100,
+        ),
+    ]
+}
+
+impl VipsImage {
+    fn exif_field(&self, ifd: u8, tag_name: &str) -> Option<String> {
+        self.get_string(
+            field_name(
+                ifd,
+                tag_name,
+            ),
+        )
+        .ok()
+        .map(|field| raw_value(&field).to_string())
+    }
+
+    /// Returns a typed view of the common EXIF tags, parsed from the `exif-ifd*` metadata fields.
+    pub fn exif(&self) -> Exif {
+        let gps = match (
+            self.exif_field(
+                IFD_GPS,
+                "GPSLatitude",
+            )
+            .as_deref()
+            .and_then(degrees_from_dms),
+            self.exif_field(
+                IFD_GPS,
+                "GPSLongitude",
+            )
+            .as_deref()
+            .and_then(degrees_from_dms),
+        ) {
+            (Some(latitude), Some(longitude)) => {
+                let south = self
+                    .exif_field(
+                        IFD_GPS,
+                        "GPSLatitudeRef",
+                    )
+                    .is_some_and(|r| r.starts_with('S'));
+                let west = self
+                    .exif_field(
+                        IFD_GPS,
+                        "GPSLongitudeRef",
+                    )
+                    .is_some_and(|r| r.starts_with('W'));
+                let below_sea_level = self
+                    .exif_field(
+                        IFD_GPS,
+                        "GPSAltitudeRef",
+                    )
+                    .is_some_and(|r| r.trim() == "1");
+                let altitude = self
+                    .exif_field(
+                        IFD_GPS,
+                        "GPSAltitude",
+                    )
+                    .as_deref()
+                    .and_then(parse_rational)
+                    .map(
+                        |altitude| {
+                            if below_sea_level {
+                                -altitude
+                            } else {
+                                altitude
+                            }
+                        },
+                    );
+                Some(
+                    GpsCoordinates {
+                        latitude: if south { -latitude } else { latitude },
+                        longitude: if west { -longitude } else { longitude },
+                        altitude,
+                    },
+                )
+            }
+            _ => None,
+        };
+
+        Exif {
+            orientation: self
+                .exif_field(
+                    IFD0,
+                    "Orientation",
+                )
+                .and_then(|value| {
+                    value
+                        .parse()
+                        .ok()
+                }),
+            datetime: self.exif_field(
+                IFD0,
+                "DateTime",
+            ),
+            datetime_original: self.exif_field(
+                IFD_EXIF,
+                "DateTimeOriginal",
+            ),
+            make: self.exif_field(
+                IFD0,
+                "Make",
+            ),
+            model: self.exif_field(
+                IFD0,
+                "Model",
+            ),
+            exposure_time: self
+                .exif_field(
+                    IFD_EXIF,
+                    "ExposureTime",
+                )
+                .as_deref()
+                .and_then(parse_rational),
+            f_number: self
+                .exif_field(
+                    IFD_EXIF,
+                    "FNumber",
+                )
+                .as_deref()
+                .and_then(parse_rational),
+            iso: self
+                .exif_field(
+                    IFD_EXIF,
+                    "ISOSpeedRatings",
+                )
+                .and_then(|value| {
+                    value
+                        .split_whitespace()
+                        .next()
+                        .and_then(|iso| {
+                            iso.parse()
+                                .ok()
+                        })
+                }),
+            gps,
+        }
+    }
+
+    /// Parses the `exif-data` blob, or returns an empty one if the image has none.
+    fn exif_blob(&self) -> Result<ExifBlob> {
+        match self.get_blob(EXIF_DATA) {
+            Ok(data) => ExifBlob::parse(&data),
+            Err(_) => Ok(ExifBlob::default()),
+        }
+    }
+
+    fn set_exif_blob(&mut self, blob: &ExifBlob) -> Result<()> {
+        self.set_blob_copy(
+            EXIF_DATA,
+            &blob.to_bytes(),
+        )
+    }
+
+    fn set_exif_ascii(&mut self, ifd: u8, tag: u16, tag_name: &str, value: &str) -> Result<()> {
+        let mut blob = self.exif_blob()?;
+        blob.ifd_mut(ifd)
+            .set(Entry::ascii(tag, value));
+        let size = value.len() + 1;
+        self.set_string(
+            field_name(
+                ifd,
+                tag_name,
+            ),
+            &format!(
+                "{} ({}, ASCII, {} components, {} bytes)",
+                value, value, size, size
+            ),
+        )?;
+        self.set_exif_blob(&blob)
+    }
+
+    fn set_exif_rational(
+        &mut self,
+        ifd: u8,
+        tag: u16,
+        tag_name: &str,
+        value: f64,
+        description: &str,
+    ) -> Result<()> {
+        let (numerator, denominator) = to_rational(value);
+        let mut blob = self.exif_blob()?;
+        let big_endian = blob.big_endian;
+        blob.ifd_mut(ifd)
+            .set(
+                Entry::rationals(
+                    tag,
+                    &[(
+                        numerator,
+                        denominator,
+                    )],
+                    big_endian,
+                ),
+            );
+        self.set_string(
+            field_name(
+                ifd,
+                tag_name,
+            ),
+            &format!(
+                "{}/{} ({}, Rational, 1 components, 8 bytes)",
+                numerator, denominator, description
+            ),
+        )?;
+        self.set_exif_blob(&blob)
+    }
+
+    /// Sets the EXIF orientation, from 1 (top-left) to 8 (left-bottom), in the metadata fields and the `exif-data` blob.
+    /// The `orientation` field which savers use is updated too.
+    pub fn set_exif_orientation(&mut self, orientation: u16) -> Result<()> {
+        if !(1..=8).contains(&orientation) {
+            return Err(
+                Error::OperationError(format!(
+                    "Invalid orientation: {}",
+                    orientation
+                )),
+            );
+        }
+        let mut blob = self.exif_blob()?;
+        let big_endian = blob.big_endian;
+        blob.ifd0
+            .set(
+                Entry::shorts(
+                    TAG_ORIENTATION,
+                    &[orientation],
+                    big_endian,
+                ),
+            );
+        self.set_string(
+            field_name(
+                IFD0,
+                "Orientation",
+            ),
+            &format!(
+                "{} ({}, Short, 1 components, 2 bytes)",
+                orientation, orientation
+            ),
+        )?;
+        self.set_int(
+            "orientation",
+            orientation as i32,
+        )?;
+        self.set_exif_blob(&blob)
+    }
+
+    /// Sets the EXIF date and time of the last change, as `YYYY:MM:DD HH:MM:SS`.
+    pub fn set_exif_datetime(&mut self, datetime: &str) -> Result<()> {
+        self.set_exif_ascii(
+            IFD0,
+            TAG_DATETIME,
+            "DateTime",
+            datetime,
+        )
+    }
+
+    /// Sets the EXIF date and time when the picture was taken, as `YYYY:MM:DD HH:MM:SS`.
+    pub fn set_exif_datetime_original(&mut self, datetime: &str) -> Result<()> {
+        self.set_exif_ascii(
+            IFD_EXIF,
+            TAG_DATETIME_ORIGINAL,
+            "DateTimeOriginal",
+            datetime,
+        )
+    }
+
+    /// Sets the EXIF camera manufacturer.
+    pub fn set_exif_make(&mut self, make: &str) -> Result<()> {
+        self.set_exif_ascii(
+            IFD0,
+            TAG_MAKE,
+            "Make",
+            make,
+        )
+    }
+
+    /// Sets the EXIF camera model.
+    pub fn set_exif_model(&mut self, model: &str) -> Result<()> {
+        self.set_exif_ascii(
+            IFD0,
+            TAG_MODEL,
+            "Model",
+            model,
+        )
+    }
+
+    /// Sets the EXIF exposure time in seconds.
+    pub fn set_exif_exposure_time(&mut self, seconds: f64) -> Result<()> {
+        self.set_exif_rational(
+            IFD_EXIF,
+            TAG_EXPOSURE_TIME,
+            "ExposureTime",
+            seconds,
+            &format!(
+                "{} sec.",
+                seconds
+            ),
+        )
+    }
+
+    /// Sets the EXIF F number.
+    pub fn set_exif_f_number(&mut self, f_number: f64) -> Result<()> {
+        self.set_exif_rational(
+            IFD_EXIF,
+            TAG_F_NUMBER,
+            "FNumber",
+            f_number,
+            &format!(
+                "f/{:.1}",
+                f_number
+            ),
+        )
+    }
+
+    /// Sets the EXIF ISO speed.
+    pub fn set_exif_iso(&mut self, iso: u16) -> Result<()> {
+        let mut blob = self.exif_blob()?;
+        let big_endian = blob.big_endian;
+        blob.ifd_mut(IFD_EXIF)
+            .set(
+                Entry::shorts(
+                    TAG_ISO,
+                    &[iso],
+                    big_endian,
+                ),
+            );
+        self.set_string(
+            field_name(
+                IFD_EXIF,
+                "ISOSpeedRatings",
+            ),
+            &format!(
+                "{} ({}, Short, 1 components, 2 bytes)",
+                iso, iso
+            ),
+        )?;
+        self.set_exif_blob(&blob)
+    }
+
+    /// Sets the EXIF GPS position in the metadata fields and the `exif-data` blob.
+    pub fn set_exif_gps(&mut self, gps: GpsCoordinates) -> Result<()> {
+        let latitude = dms_from_degrees(gps.latitude);
+        let longitude = dms_from_degrees(gps.longitude);
+        let latitude_ref = if gps.latitude < 0.0 { "S" } else { "N" };
+        let longitude_ref = if gps.longitude < 0.0 { "W" } else { "E" };
+        let altitude = gps
+            .altitude
+            .map(
+                |altitude| {
+                    (
+                        u8::from(altitude < 0.0),
+                        to_rational(altitude),
+                    )
+                },
+            );
+        let mut blob = self.exif_blob()?;
+        let big_endian = blob.big_endian;
+        let ifd = blob
+            .gps
+            .get_or_insert_with(Ifd::default);
+        if ifd
+            .get(TAG_GPS_VERSION_ID)
+            .is_none()
+        {
+            ifd.set(Entry {
+                tag: TAG_GPS_VERSION_ID,
+                format: FORMAT_BYTE,
+                count: 4,
+                data: vec![2, 2, 0, 0],
+                raw: false,
+            });
+        }
+        ifd.set(
+            Entry::ascii(
+                TAG_GPS_LATITUDE_REF,
+                latitude_ref,
+            ),
+        );
+        ifd.set(
+            Entry::rationals(
+                TAG_GPS_LATITUDE,
+                &latitude,
+                big_endian,
+            ),
+        );
+        ifd.set(
+            Entry::ascii(
+                TAG_GPS_LONGITUDE_REF,
+                longitude_ref,
+            ),
+        );
+        ifd.set(
+            Entry::rationals(
+                TAG_GPS_LONGITUDE,
+                &longitude,
+                big_endian,
+            ),
+        );
+        match altitude {
+            Some((altitude_ref, rational)) => {
+                ifd.set(Entry {
+                    tag: TAG_GPS_ALTITUDE_REF,
+                    format: FORMAT_BYTE,
+                    count: 1,
+                    data: vec![altitude_ref],
+                    raw: false,
+                });
+                ifd.set(
+                    Entry::rationals(
+                        TAG_GPS_ALTITUDE,
+                        &[rational],
+                        big_endian,
+                    ),
+                );
+            }
+            None => {
+                ifd.remove(TAG_GPS_ALTITUDE_REF);
+                ifd.remove(TAG_GPS_ALTITUDE);
+            }
+        }
+
+        let dms_string = |dms: &[(u32, u32); 3]| {
+            format!(
+                "{}/{} {}/{} {}/{} ({}, {}, {:.2}, Rational, 3 components, 24 bytes)",
+                dms[0].0,
+                dms[0].1,
+                dms[1].0,
+                dms[1].1,
+                dms[2].0,
+                dms[2].1,
+                dms[0].0,
+                dms[1].0,
+                dms[2].0 as f64 / dms[2].1 as f64
+            )
+        };
+
+        self.set_string(
+            field_name(
+                IFD_GPS,
+                "GPSLatitudeRef",
+            ),
+            &format!(
+                "{} ({}, ASCII, 2 components, 2 bytes)",
+                latitude_ref, latitude_ref
+            ),
+        )?;
+        self.set_string(
+            field_name(
+                IFD_GPS,
+                "GPSLatitude",
+            ),
+            &dms_string(&latitude),
+        )?;
+        self.set_string(
+            field_name(
+                IFD_GPS,
+                "GPSLongitudeRef",
+            ),
+            &format!(
+                "{} ({}, ASCII, 2 components, 2 bytes)",
+                longitude_ref, longitude_ref
+            ),
+        )?;
+        self.set_string(
+            field_name(
+                IFD_GPS,
+                "GPSLongitude",
+            ),
+            &dms_string(&longitude),
+        )?;
+
+        match altitude {
+            Some((altitude_ref, (numerator, denominator))) => {
+                self.set_string(
+                    field_name(
+                        IFD_GPS,
+                        "GPSAltitudeRef",
+                    ),
+                    &format!(
+                        "{} ({}, Byte, 1 components, 1 bytes)",
+                        altitude_ref, altitude_ref
+                    ),
+                )?;
+                self.set_string(
+                    field_name(
+                        IFD_GPS,
+                        "GPSAltitude",
+                    ),
+                    &format!(
+                        "{}/{} ({:.2} m, Rational, 1 components, 8 bytes)",
+                        numerator,
+                        denominator,
+                        numerator as f64 / denominator as f64
+                    ),
+                )?;
+            }
+            None => {
+                self.remove(
+                    field_name(
+                        IFD_GPS,
+                        "GPSAltitudeRef",
+                    ),
+                )?;
+                self.remove(
+                    field_name(
+                        IFD_GPS,
+                        "GPSAltitude",
+                    ),
+                )?;
+            }
+        }
+
+        self.set_exif_blob(&blob)
+    }
+
+    /// Removes all EXIF GPS tags from the metadata fields and the `exif-data` blob.
+    pub fn remove_exif_gps(&mut self) -> Result<()> {
+        let blob = match self.get_blob(EXIF_DATA) {
+            Ok(data) => Some(ExifBlob::parse(&data)?),
+            Err(_) => None,
+        };
+        let prefix = format!(
+            "exif-ifd{}-",
+            IFD_GPS
+        );
+        for name in self.get_fields() {
+            if name.starts_with(&prefix) {
+                self.remove(&name)?;
+            }
+        }
+        if let Some(mut blob) = blob {
+            blob.gps = None;
+            self.set_exif_blob(&blob)?;
+        }
+        Ok(())
+    }
+}
+
+/// An IFD entry. `data` holds the value in the byte order of the blob, or the 4 bytes of the value field as read
+/// when `raw` is set.
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    tag: u16,
+    format: u16,
+    count: u32,
+    data: Vec<u8>,
+    /// The value is kept as read, as it is of an unknown format or holds offsets into the TIFF data
+    raw: bool,
+}
+
+impl Entry {
+    fn ascii(tag: u16, value: &str) -> Entry {
+        let mut data = value
+            .as_bytes()
+            .to_vec();
+        data.push(0);
+        Entry {
+            tag,
+            format: FORMAT_ASCII,
+            count: data.len() as u32,
+            data,
+            raw: false,
+        }
+    }
+
+    fn shorts(tag: u16, values: &[u16], big_endian: bool) -> Entry {
+        Entry {
+            tag,
+            format: FORMAT_SHORT,
+            count: values.len() as u32,
+            data: values
+                .iter()
+                .flat_map(|value| {
+                    put_u16(
+                        *value,
+                        big_endian,
+                    )
+                })
+                .collect(),
+            raw: false,
+        }
+    }
+
+    fn long(tag: u16, value: u32, big_endian: bool) -> Entry {
+        Entry {
+            tag,
+            format: FORMAT_LONG,
+            count: 1,
+            data: put_u32(
+                value,
+                big_endian,
+            )
+            .to_vec(),
+            raw: false,
+        }
+    }
+
+    fn rationals(tag: u16, values: &[(u32, u32)], big_endian: bool) -> Entry {
+        Entry {
+            tag,
+            format: FORMAT_RATIONAL,
+            count: values.len() as u32,
+            data: values
+                .iter()
+                .flat_map(
+                    |(numerator, denominator)| {
+                        let mut bytes = put_u32(
+                            *numerator,
+                            big_endian,
+                        )
+                        .to_vec();
+                        bytes.extend(put_u32(
+                            *denominator,
+                            big_endian,
+                        ));
+                        bytes
+                    },
+                )
+                .collect(),
+            raw: false,
+        }
+    }
+
+    /// Size of the value in the data area after the IFD
+    fn data_len(&self) -> usize {
+        if self.raw
+            || self
+                .data
+                .len()
+                <= 4
+        {
+            0
+        } else {
+            self.data
+                .len()
+                + self
+                    .data
+                    .len()
+                    % 2
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Ifd {
+    entries: Vec<Entry>,
+}
+
+impl Ifd {
+    fn get(&self, tag: u16) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.tag == tag)
+    }
+
+    fn set(&mut self, entry: Entry) {
+        self.remove(entry.tag);
+        self.entries
+            .push(entry);
+        self.entries
+            .sort_by_key(|entry| entry.tag);
+    }
+
+    fn remove(&mut self, tag: u16) {
+        self.entries
+            .retain(|entry| entry.tag != tag);
+    }
+
+    /// Size of the IFD including the data area of values which do not fit in an entry
+    fn len(&self) -> usize {
+        2 + 12
+            * self
+                .entries
+                .len()
+            + 4
+            + self
+                .entries
+                .iter()
+                .map(Entry::data_len)
+                .sum::<usize>()
+    }
+
+    fn has_raw(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.raw)
+    }
+}
+
+/// The TIFF structure of an `exif-data` blob: IFD0, the Exif, Interop and GPS sub-IFDs, and IFD1 with its thumbnail.
+/// Pointer tags are dropped on parse and regenerated on write.
+///
+/// Values are relocated on write, except raw entries such as the MakerNote, whose contents may hold offsets.
+/// When there are raw entries, the original TIFF data is written unchanged with the new IFDs appended after it,
+/// so that everything raw entries point at stays at its offset.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ExifBlob {
+    big_endian: bool,
+    ifd0: Ifd,
+    exif: Option<Ifd>,
+    interop: Option<Ifd>,
+    gps: Option<Ifd>,
+    ifd1: Option<Ifd>,
+    thumbnail: Option<Vec<u8>>,
+    /// Offset of the thumbnail in `original`
+    thumbnail_offset: Option<u32>,
+    /// The TIFF data the blob was parsed from
+    original: Vec<u8>,
+}
+
+fn put_u16(value: u16, big_endian: bool) -> [u8; 2] {
+    if big_endian {
+        value.to_be_bytes()
+    } else {
+        value.to_le_bytes()
+    }
+}
+
+fn put_u32(value: u32, big_endian: bool) -> [u8; 4] {
+    if big_endian {
+        value.to_be_bytes()
+    } else {
+        value.to_le_bytes()
+    }
+}
+
+fn format_size(format: u16) -> Option<usize> {
+    match format {
+        1 | 2 | 6 | 7 => Some(1),
+        3 | 8 => Some(2),
+        4 | 9 | 11 => Some(4),
+        5 | 10 | 12 => Some(8),
+        _ => None,
+    }
+}
+
+struct Reader<'a> {
+    tiff: &'a [u8],
+    big_endian: bool,
+}
+
+impl Reader<'_> {
+    fn bytes(&self, offset: usize, len: usize) -> Result<&[u8]> {
+        offset
+            .checked_add(len)
+            .and_then(|end| {
+                self.tiff
+                    .get(offset..end)
+            })
+            .ok_or(Error::IOError("Truncated EXIF data".to_string()))
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16> {
+        let bytes: [u8; 2] = self
+            .bytes(offset, 2)?
+            .try_into()
+            .unwrap();
+        Ok(if self.big_endian { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) })
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32> {
+        let bytes: [u8; 4] = self
+            .bytes(offset, 4)?
+            .try_into()
+            .unwrap();
+        Ok(if self.big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    }
+
+    fn entry_u32(&self, entry: &Entry) -> Result<u32> {
+        let reader = Reader {
+            tiff: &entry.data,
+            big_endian: self.big_endian,
+        };
+        match entry.format {
+            FORMAT_SHORT => reader
+                .u16(0)
+                .map(u32::from),
+            _ => reader.u32(0),
+        }
+    }
+
+    /// Reads the IFD at the offset and returns it with the offset of the next IFD.
+    fn ifd(&self, offset: usize) -> Result<(Ifd, u32)> {
+        let count = self.u16(offset)? as usize;
+        let mut entries = Vec::with_capacity(count);
+        for i in 0..count {
+            let position = offset + 2 + i * 12;
+            let tag = self.u16(position)?;
+            let format = self.u16(position + 2)?;
+            let count = self.u32(position + 4)?;
+            let Some(size) = format_size(format).filter(|_| !OFFSET_TAGS.contains(&tag)) else {
+                entries.push(Entry {
+                    tag,
+                    format,
+                    count,
+                    data: self
+                        .bytes(
+                            position + 8,
+                            4,
+                        )?
+                        .to_vec(),
+                    raw: true,
+                });
+                continue;
+            };
+            let len = size
+                .checked_mul(count as usize)
+                .ok_or(Error::IOError("Invalid EXIF entry".to_string()))?;
+            let data = if len <= 4 {
+                self.bytes(
+                    position + 8,
+                    len,
+                )?
+            } else {
+                self.bytes(
+                    self.u32(position + 8)? as usize,
+                    len,
+                )?
+            };
+            entries.push(Entry {
+                tag,
+                format,
+                count,
+                data: data.to_vec(),
+                raw: false,
+            });
+        }
+        let next = self.u32(offset + 2 + count * 12)?;
+        Ok((
+            Ifd {
+                entries,
+            },
+            next,
+        ))
+    }
+
+    /// Reads the sub-IFD the pointer tag points at and removes the pointer tag from the parent.
+    fn sub_ifd(&self, parent: &mut Ifd, tag: u16) -> Result<Option<Ifd>> {
+        let Some(pointer) = parent
+            .get(tag)
+            .cloned()
+        else {
+            return Ok(None);
+        };
+        parent.remove(tag);
+        let offset = self.entry_u32(&pointer)? as usize;
+        Ok(Some(
+            self.ifd(offset)?
+                .0,
+        ))
+    }
+}
+
+impl ExifBlob {
+    pub(crate) fn parse(data: &[u8]) -> Result<ExifBlob> {
+        let tiff = data
+            .strip_prefix(EXIF_HEADER)
+            .unwrap_or(data);
+        let big_endian = match tiff.get(..4) {
+            Some(b"II*\0") => false,
+            Some(b"MM\0*") => true,
+            _ => return Err(Error::IOError("Invalid EXIF header".to_string())),
+        };
+        let reader = Reader {
+            tiff,
+            big_endian,
+        };
+
+        let (mut ifd0, next) = reader.ifd(reader.u32(4)? as usize)?;
+        let mut exif = reader.sub_ifd(
+            &mut ifd0,
+            TAG_EXIF_IFD,
+        )?;
+        let interop = match exif.as_mut() {
+            Some(exif) => reader.sub_ifd(
+                exif,
+                TAG_INTEROP_IFD,
+            )?,
+            None => None,
+        };
+        let gps = reader.sub_ifd(
+            &mut ifd0,
+            TAG_GPS_IFD,
+        )?;
+
+        let mut thumbnail = None;
+        let mut thumbnail_offset = None;
+        let ifd1 = if next == 0 {
+            None
+        } else {
+            let (mut ifd1, _) = reader.ifd(next as usize)?;
+            if let (Some(offset), Some(length)) = (
+                ifd1.get(TAG_THUMBNAIL_OFFSET)
+                    .cloned(),
+                ifd1.get(TAG_THUMBNAIL_LENGTH)
+                    .cloned(),
+            ) {
+                let offset = reader.entry_u32(&offset)?;
+                thumbnail = Some(
+                    reader
+                        .bytes(
+                            offset as usize,
+                            reader.entry_u32(&length)? as usize,
+                        )?
+                        .to_vec(),
+                );
+                thumbnail_offset = Some(offset);
+            }
+            ifd1.remove(TAG_THUMBNAIL_OFFSET);
+            ifd1.remove(TAG_THUMBNAIL_LENGTH);
+            Some(ifd1)
+        };
+
+        Ok(ExifBlob {
+            big_endian,
+            ifd0,
+            exif,
+            interop,
+            gps,
+            ifd1,
+            thumbnail,
+            thumbnail_offset,
+            original: tiff.to_vec(),
+        })
+    }
+
+    fn ifd_mut(&mut self, ifd: u8) -> &mut Ifd {
+        match ifd {
+            IFD_EXIF => self
+                .exif
+                .get_or_insert_with(Ifd::default),
+            IFD_GPS => self
+                .gps
+                .get_or_insert_with(Ifd::default),
+            _ => &mut self.ifd0,
+        }
+    }
+
+    fn write_ifd(out: &mut Vec<u8>, ifd: &Ifd, next: u32, big_endian: bool) {
+        let start = out.len();
+        let entries_end = start
+            + 2
+            + 12 * ifd
+                .entries
+                .len()
+            + 4;
+        let mut data_area: Vec<u8> = Vec::new();
+
+        out.extend(put_u16(
+            ifd.entries
+                .len() as u16,
+            big_endian,
+        ));
+        for entry in &ifd.entries {
+            out.extend(put_u16(
+                entry.tag,
+                big_endian,
+            ));
+            out.extend(put_u16(
+                entry.format,
+                big_endian,
+            ));
+            out.extend(put_u32(
+                entry.count,
+                big_endian,
+            ));
+            if entry.raw {
+                out.extend(&entry.data);
+            } else if entry
+                .data
+                .len()
+                <= 4
+            {
+                let mut value = entry
+                    .data
+                    .clone();
+                value.resize(4, 0);
+                out.extend(value);
+            } else {
+                out.extend(put_u32(
+                    (entries_end + data_area.len()) as u32,
+                    big_endian,
+                ));
+                data_area.extend(&entry.data);
+                // Values start on a word boundary
+                if data_area.len() % 2 == 1 {
+                    data_area.push(0);
+                }
+            }
+        }
+        out.extend(put_u32(
+            next,
+            big_endian,
+        ));
+        out.extend(data_area);
+    }
+
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let big_endian = self.big_endian;
+        let keep_original = !self
+            .original
+            .is_empty()
+            && [
+                Some(&self.ifd0),
+                self.exif
+                    .as_ref(),
+                self.interop
+                    .as_ref(),
+                self.gps
+                    .as_ref(),
+                self.ifd1
+                    .as_ref(),
+            ]
+            .into_iter()
+            .flatten()
+            .any(Ifd::has_raw);
+        let kept_thumbnail_offset = self
+            .thumbnail_offset
+            .filter(|_| keep_original);
+
+        let mut tiff = if keep_original {
+            self.original
+                .clone()
+        } else if big_endian {
+            b"MM\0*\0\0\0\0".to_vec()
+        } else {
+            b"II*\0\0\0\0\0".to_vec()
+        };
+        // IFDs start on a word boundary
+        if tiff.len() % 2 == 1 {
+            tiff.push(0);
+        }
+        let mut ifd0 = self
+            .ifd0
+            .clone();
+        let mut exif = self
+            .exif
+            .clone();
+        let mut ifd1 = self
+            .ifd1
+            .clone();
+
+        // Add pointer tags with placeholder values first so that the sizes of the IFDs are final
+        if exif.is_some() {
+            ifd0.set(
+                Entry::long(
+                    TAG_EXIF_IFD,
+                    0,
+                    big_endian,
+                ),
+            );
+        }
+        if self
+            .gps
+            .is_some()
+        {
+            ifd0.set(
+                Entry::long(
+                    TAG_GPS_IFD,
+                    0,
+                    big_endian,
+                ),
+            );
+        }
+        if let (Some(exif), Some(_)) = (
+            exif.as_mut(),
+            &self.interop,
+        ) {
+            exif.set(
+                Entry::long(
+                    TAG_INTEROP_IFD,
+                    0,
+                    big_endian,
+                ),
+            );
+        }
+        if let Some(thumbnail) = &self.thumbnail {
+            let ifd1 = ifd1.get_or_insert_with(Ifd::default);
+            ifd1.set(
+                Entry::long(
+                    TAG_THUMBNAIL_OFFSET,
+                    0,
+                    big_endian,
+                ),
+            );
+            ifd1.set(
+                Entry::long(
+                    TAG_THUMBNAIL_LENGTH,
+                    thumbnail.len() as u32,
+                    big_endian,
+                ),
+            );
+        }
+
+        let len = |ifd: &Option<Ifd>| {
+            ifd.as_ref()
+                .map_or(
+                    0,
+                    Ifd::len,
+                )
+        };
+        let ifd0_offset = tiff.len();
+        let exif_offset = ifd0_offset + ifd0.len();
+        let interop_offset = exif_offset + len(&exif);
+        let gps_offset = interop_offset + len(&self.interop);
+        let ifd1_offset = gps_offset + len(&self.gps);
+        let thumbnail_offset = kept_thumbnail_offset.map_or(
+            ifd1_offset + len(&ifd1),
+            |offset| offset as usize,
+        );
+
+        if exif.is_some() {
+            ifd0.set(
+                Entry::long(
+                    TAG_EXIF_IFD,
+                    exif_offset as u32,
+                    big_endian,
+                ),
+            );
+        }
+        if self
+            .gps
+            .is_some()
+        {
+            ifd0.set(
+                Entry::long(
+                    TAG_GPS_IFD,
+                    gps_offset as u32,
+                    big_endian,
+                ),
+            );
+        }
+        if let (Some(exif), Some(_)) = (
+            exif.as_mut(),
+            &self.interop,
+        ) {
+            exif.set(
+                Entry::long(
+                    TAG_INTEROP_IFD,
+                    interop_offset as u32,
+                    big_endian,
+                ),
+            );
+        }
+        if let (Some(ifd1), Some(_)) = (
+            ifd1.as_mut(),
+            &self.thumbnail,
+        ) {
+            ifd1.set(
+                Entry::long(
+                    TAG_THUMBNAIL_OFFSET,
+                    thumbnail_offset as u32,
+                    big_endian,
+                ),
+            );
+        }
+
+        tiff[4..8].copy_from_slice(&put_u32(
+            ifd0_offset as u32,
+            big_endian,
+        ));
+        Self::write_ifd(
+            &mut tiff,
+            &ifd0,
+            if ifd1.is_some() { ifd1_offset as u32 } else { 0 },
+            big_endian,
+        );
+        for ifd in [&exif, &self.interop, &self.gps]
+            .into_iter()
+            .flatten()
+        {
+            Self::write_ifd(
+                &mut tiff,
+                ifd,
+                0,
+                big_endian,
+            );
+        }
+        if let Some(ifd1) = &ifd1 {
+            Self::write_ifd(
+                &mut tiff,
+                ifd1,
+                0,
+                big_endian,
+            );
+        }
+        if let (Some(thumbnail), None) = (
+            &self.thumbnail,
+            kept_thumbnail_offset,
+        ) {
+            tiff.extend(thumbnail);
+        }
+
+        let mut data = EXIF_HEADER.to_vec();
+        data.extend(tiff);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THUMBNAIL: &[u8] = &[0xFF, 0xD8, 0xFF, 0xD9, 0x01];
+
+    fn sample(big_endian: bool) -> ExifBlob {
+        let mut blob = ExifBlob {
+            big_endian,
+            ..ExifBlob::default()
+        };
+        blob.ifd0
+            .set(
+                Entry::ascii(
+                    TAG_MAKE,
+                    "Canon",
+                ),
+            );
+        blob.ifd0
+            .set(
+                Entry::shorts(
+                    TAG_ORIENTATION,
+                    &[6],
+                    big_endian,
+                ),
+            );
+        blob.ifd_mut(IFD_EXIF)
+            .set(
+                Entry::rationals(
+                    TAG_EXPOSURE_TIME,
+                    &[(1, 250)],
+                    big_endian,
+                ),
+            );
+        blob.ifd_mut(IFD_EXIF)
+            .set(
+                Entry::ascii(
+                    TAG_DATETIME_ORIGINAL,
+                    "2024:05:01 12:00:00",
+                ),
+            );
+        blob.interop = Some(Ifd {
+            entries: vec![Entry::ascii(
+                0x0001,
+                "R98",
+            )],
+        });
+        blob.ifd_mut(IFD_GPS)
+            .set(
+                Entry::rationals(
+                    TAG_GPS_LATITUDE,
+                    &[(35, 1), (40, 1), (1234, 100)],
+                    big_endian,
+                ),
+            );
+        blob.ifd1 = Some(Ifd {
+            entries: vec![Entry::shorts(
+                0x0103,
+                &[6],
+                big_endian,
+            )],
+        });
+        blob.thumbnail = Some(THUMBNAIL.to_vec());
+        blob
+    }
+
+    fn assert_same_tags(left: &ExifBlob, right: &ExifBlob) {
+        assert_eq!(
+            left.big_endian,
+            right.big_endian
+        );
+        assert_eq!(
+            left.ifd0,
+            right.ifd0
+        );
+        assert_eq!(
+            left.exif,
+            right.exif
+        );
+        assert_eq!(
+            left.interop,
+            right.interop
+        );
+        assert_eq!(
+            left.gps,
+            right.gps
+        );
+        assert_eq!(
+            left.ifd1,
+            right.ifd1
+        );
+        assert_eq!(
+            left.thumbnail,
+            right.thumbnail
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for big_endian in [false, true] {
+            let blob = sample(big_endian);
+            let bytes = blob.to_bytes();
+            assert!(bytes.starts_with(EXIF_HEADER));
+            let parsed = ExifBlob::parse(&bytes).unwrap();
+            assert_same_tags(
+                &blob,
+                &parsed,
+            );
+            assert_eq!(
+                parsed.to_bytes(),
+                bytes
+            );
+        }
+    }
+
+    #[test]
+    fn parse_without_header() {
+        let bytes = sample(false).to_bytes();
+        let parsed = ExifBlob::parse(&bytes[EXIF_HEADER.len()..]).unwrap();
+        assert_same_tags(
+            &sample(false),
+            &parsed,
+        );
+    }
+
+    #[test]
+    fn maker_note_stays_in_place() {
+        let maker_note: Vec<u8> = (0..32).collect();
+        let mut blob = sample(false);
+        blob.ifd_mut(IFD_EXIF)
+            .set(Entry {
+                tag: TAG_MAKER_NOTE,
+                format: 7,
+                count: maker_note.len() as u32,
+                data: maker_note.clone(),
+                raw: false,
+            });
+        let bytes = blob.to_bytes();
+        let mut parsed = ExifBlob::parse(&bytes).unwrap();
+        let entry = parsed
+            .exif
+            .as_ref()
+            .and_then(|exif| exif.get(TAG_MAKER_NOTE))
+            .cloned()
+            .unwrap();
+        assert!(entry.raw);
+        let offset = u32::from_le_bytes(
+            entry.data[..4]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+
+        parsed
+            .ifd_mut(IFD0)
+            .set(
+                Entry::ascii(
+                    TAG_MODEL,
+                    "A much longer model name than before",
+                ),
+            );
+        let rewritten = parsed.to_bytes();
+        let tiff = &rewritten[EXIF_HEADER.len()..];
+        assert_eq!(
+            &tiff[offset..offset + maker_note.len()],
+            maker_note.as_slice()
+        );
+
+        let reparsed = ExifBlob::parse(&rewritten).unwrap();
+        assert_eq!(
+            reparsed
+                .exif
+                .as_ref()
+                .and_then(|exif| exif.get(TAG_MAKER_NOTE)),
+            Some(&entry)
+        );
+        assert!(reparsed
+            .ifd0
+            .get(TAG_MODEL)
+            .is_some());
+        assert_eq!(
+            reparsed.thumbnail,
+            parsed.thumbnail
+        );
+    }
+
+    #[test]
+    fn unknown_format_is_kept() {
+        let mut blob = sample(false);
+        let unknown = Entry {
+            tag: 0x1234,
+            format: 99,
+            count: 1,
+            data: vec![1, 2, 3, 4],
+            raw: true,
+        };
+        blob.ifd0
+            .set(unknown.clone());
+        let parsed = ExifBlob::parse(&blob.to_bytes()).unwrap();
+        assert_eq!(
+            parsed
+                .ifd0
+                .get(0x1234),
+            Some(&unknown)
+        );
+    }
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let bytes = sample(true).to_bytes();
+        for len in 0..bytes.len() - THUMBNAIL.len() {
+            assert!(
+                ExifBlob::parse(&bytes[..len]).is_err(),
+                "{} bytes",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_input_is_an_error() {
+        assert!(ExifBlob::parse(b"").is_err());
+        assert!(ExifBlob::parse(b"Exif\0\0XX*\0\x08\0\0\0").is_err());
+        // IFD0 beyond the end of the data
+        assert!(ExifBlob::parse(b"II*\0\xFF\0\0\0").is_err());
+        // More entries than there are bytes
+        assert!(ExifBlob::parse(b"II*\0\x08\0\0\0\xFF\xFF").is_err());
+        // A value pointing beyond the end of the data
+        assert!(
+            ExifBlob::parse(b"II*\0\x08\0\0\0\x01\0\x0F\x01\x02\0\x10\0\0\0\xF0\0\0\0\0\0\0\0")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn dms_carries_rounded_seconds() {
+        assert_eq!(
+            dms_from_degrees(10.999_999),
+            [(11, 1), (0, 1), (0, 100)]
+        );
+        assert_eq!(
+            dms_from_degrees(-0.5),
+            [(0, 1), (30, 1), (0, 100)]
+        );
+        assert_eq!(
+            dms_from_degrees(35.667_284),
+            [(35, 1), (40, 1), (222, 100)]
+        );
+    }
+}
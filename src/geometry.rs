@@ -0,0 +1,479 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{voption::VOption, Result, VipsImage, VipsRegion};
+
+/// A position in pixels
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point {
+            x,
+            y,
+        }
+    }
+}
+
+/// A width and height in pixels
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dimensions {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Dimensions {
+    pub fn new(width: i32, height: i32) -> Dimensions {
+        Dimensions {
+            width,
+            height,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+}
+
+/// A rectangle in pixels. The right and bottom edges are exclusive, as in libvips' `VipsRect`.
+///
+/// ```no_run
+/// use rs_vips::{Rect, VipsImage};
+///
+/// let image = VipsImage::new_from_file("input.jpg").unwrap();
+/// // Keep the part of the crop box which lies inside the image
+/// let area = Rect::new(-20, 40, 300, 200)
+///     .clamp_to_image(&image)
+///     .unwrap();
+/// let cropped = image
+///     .crop_rect(area)
+///     .unwrap();
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn new(left: i32, top: i32, width: i32, height: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_point_size(origin: Point, size: Dimensions) -> Rect {
+        Rect::new(
+            origin.x,
+            origin.y,
+            size.width,
+            size.height,
+        )
+    }
+
+    /// The rectangle covering the whole image
+    pub fn of_image(image: &VipsImage) -> Rect {
+        Rect::new(
+            0,
+            0,
+            image.get_width(),
+            image.get_height(),
+        )
+    }
+
+    /// The top-left corner
+    pub fn origin(&self) -> Point {
+        Point::new(
+            self.left,
+            self.top,
+        )
+    }
+
+    pub fn size(&self) -> Dimensions {
+        Dimensions::new(
+            self.width,
+            self.height,
+        )
+    }
+
+    /// The first column to the right of the rectangle, saturating at `i32::MAX`
+    pub fn right(&self) -> i32 {
+        self.left
+            .saturating_add(self.width)
+    }
+
+    /// The first row below the rectangle, saturating at `i32::MAX`
+    pub fn bottom(&self) -> i32 {
+        self.top
+            .saturating_add(self.height)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size()
+            .is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.left
+            && point.x < self.right()
+            && point.y >= self.top
+            && point.y < self.bottom()
+    }
+
+    /// Returns the rectangle moved by `dx` and `dy`.
+    pub fn translate(&self, dx: i32, dy: i32) -> Rect {
+        Rect::new(
+            self.left
+                .saturating_add(dx),
+            self.top
+                .saturating_add(dy),
+            self.width,
+            self.height,
+        )
+    }
+
+    /// The area covered by both rectangles, or `None` if they do not overlap
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let left = self
+            .left
+            .max(other.left);
+        let top = self
+            .top
+            .max(other.top);
+        let right = self
+            .right()
+            .min(other.right());
+        let bottom = self
+            .bottom()
+            .min(other.bottom());
+        let rect = Rect::new(
+            left,
+            top,
+            right.saturating_sub(left),
+            bottom.saturating_sub(top),
+        );
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// The smallest rectangle enclosing both rectangles. An empty rectangle is ignored.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+        let left = self
+            .left
+            .min(other.left);
+        let top = self
+            .top
+            .min(other.top);
+        Rect::new(
+            left,
+            top,
+            self.right()
+                .max(other.right())
+                .saturating_sub(left),
+            self.bottom()
+                .max(other.bottom())
+                .saturating_sub(top),
+        )
+    }
+
+    /// The part of the rectangle inside the image, or `None` if it lies outside
+    pub fn clamp_to_image(&self, image: &VipsImage) -> Option<Rect> {
+        self.intersection(&Rect::of_image(image))
+    }
+}
+
+impl VipsImage {
+    /// The size of the image
+    pub fn size(&self) -> Dimensions {
+        Dimensions::new(
+            self.get_width(),
+            self.get_height(),
+        )
+    }
+
+    /// Extracts an area, as `extract_area`.
+    pub fn extract_rect(&self, rect: Rect) -> Result<VipsImage> {
+        self.extract_area(
+            rect.left,
+            rect.top,
+            rect.width,
+            rect.height,
+        )
+    }
+
+    /// Crops an area, as `crop`.
+    pub fn crop_rect(&self, rect: Rect) -> Result<VipsImage> {
+        self.crop(
+            rect.left,
+            rect.top,
+            rect.width,
+            rect.height,
+        )
+    }
+
+    /// Embeds the image in a canvas of `size` with its top-left corner at `position`, as `embed`.
+    pub fn embed_at(&self, position: Point, size: Dimensions) -> Result<VipsImage> {
+        self.embed(
+            position.x,
+            position.y,
+            size.width,
+            size.height,
+        )
+    }
+
+    /// Embeds the image in a canvas of `size` with its top-left corner at `position`, with `embed` options.
+    pub fn embed_at_with_opts(
+        &self,
+        position: Point,
+        size: Dimensions,
+        option: VOption,
+    ) -> Result<VipsImage> {
+        self.embed_with_opts(
+            position.x,
+            position.y,
+            size.width,
+            size.height,
+            option,
+        )
+    }
+
+    /// Inserts `sub` with its top-left corner at `position`, as `insert`.
+    pub fn insert_at(&self, sub: &VipsImage, position: Point) -> Result<VipsImage> {
+        self.insert(
+            sub,
+            position.x,
+            position.y,
+        )
+    }
+
+    /// Inserts `sub` with its top-left corner at `position`, with `insert` options.
+    pub fn insert_at_with_opts(
+        &self,
+        sub: &VipsImage,
+        position: Point,
+        option: VOption,
+    ) -> Result<VipsImage> {
+        self.insert_with_opts(
+            sub,
+            position.x,
+            position.y,
+            option,
+        )
+    }
+
+    /// Paints a rectangle, as `draw_rect`.
+    pub fn draw_rect_at(&mut self, ink: &[f64], rect: Rect) -> Result<()> {
+        self.draw_rect(
+            ink,
+            rect.left,
+            rect.top,
+            rect.width,
+            rect.height,
+        )
+    }
+
+    /// Paints a rectangle with `draw_rect` options, such as `fill`.
+    pub fn draw_rect_at_with_opts(
+        &mut self,
+        ink: &[f64],
+        rect: Rect,
+        option: VOption,
+    ) -> Result<()> {
+        self.draw_rect_with_opts(
+            ink,
+            rect.left,
+            rect.top,
+            rect.width,
+            rect.height,
+            option,
+        )
+    }
+
+    /// Returns the bounding box of the image contents, as `find_trim`.
+    pub fn find_trim_rect(&self) -> Result<Rect> {
+        let (left, top, width, height) = self.find_trim()?;
+        Ok(Rect::new(
+            left,
+            top,
+            width,
+            height,
+        ))
+    }
+
+    /// Returns the bounding box of the image contents with `find_trim` options, such as `threshold`.
+    pub fn find_trim_rect_with_opts(&self, option: VOption) -> Result<Rect> {
+        let (left, top, width, height) = self.find_trim_with_opts(option)?;
+        Ok(Rect::new(
+            left,
+            top,
+            width,
+            height,
+        ))
+    }
+}
+
+impl VipsRegion {
+    /// Generates an area of pixels and returns a copy, as `fetch`.
+    pub fn fetch_rect(&self, rect: Rect) -> Result<Vec<u8>> {
+        self.fetch(
+            rect.left,
+            rect.top,
+            rect.width,
+            rect.height,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersection() {
+        let a = Rect::new(
+            0, 0, 100, 50,
+        );
+        let b = Rect::new(
+            60, 20, 100, 100,
+        );
+        assert_eq!(
+            a.intersection(&b),
+            Some(Rect::new(
+                60, 20, 40, 30
+            ))
+        );
+        assert_eq!(
+            b.intersection(&a),
+            a.intersection(&b)
+        );
+        assert_eq!(
+            a.intersection(&a),
+            Some(a)
+        );
+        // Touching edges do not overlap, as right and bottom are exclusive
+        assert_eq!(
+            a.intersection(&Rect::new(100, 0, 10, 10)),
+            None
+        );
+        assert_eq!(
+            a.intersection(&Rect::new(0, 0, 0, 10)),
+            None
+        );
+    }
+
+    #[test]
+    fn union() {
+        let a = Rect::new(
+            0, 0, 100, 50,
+        );
+        let b = Rect::new(
+            -10, 20, 20, 100,
+        );
+        assert_eq!(
+            a.union(&b),
+            Rect::new(-10, 0, 110, 120)
+        );
+        assert_eq!(
+            b.union(&a),
+            a.union(&b)
+        );
+        assert_eq!(
+            a.union(&Rect::default()),
+            a
+        );
+        assert_eq!(
+            Rect::default().union(&a),
+            a
+        );
+    }
+
+    #[test]
+    fn contains() {
+        let rect = Rect::new(
+            10, 20, 30, 40,
+        );
+        assert!(rect.contains(Point::new(10, 20)));
+        assert!(rect.contains(Point::new(39, 59)));
+        assert!(!rect.contains(Point::new(40, 20)));
+        assert!(!rect.contains(Point::new(10, 60)));
+        assert!(!rect.contains(Point::new(9, 30)));
+        assert!(!Rect::new(0, 0, 0, 0).contains(Point::new(0, 0)));
+    }
+
+    #[test]
+    fn extreme_coordinates_saturate() {
+        let rect = Rect::new(
+            i32::MAX - 5,
+            i32::MIN,
+            100,
+            i32::MAX,
+        );
+        assert_eq!(
+            rect.right(),
+            i32::MAX
+        );
+        assert_eq!(
+            rect.bottom(),
+            -1
+        );
+        assert!(
+            rect.contains(
+                Point::new(
+                    i32::MAX - 1,
+                    -2
+                )
+            )
+        );
+        assert_eq!(
+            rect.translate(10, 0)
+                .left,
+            i32::MAX
+        );
+
+        let huge = Rect::new(
+            i32::MIN,
+            i32::MIN,
+            i32::MAX,
+            i32::MAX,
+        );
+        let union = huge.union(
+            &Rect::new(
+                i32::MAX - 10,
+                i32::MAX - 10,
+                10,
+                10,
+            ),
+        );
+        assert_eq!(
+            union.width,
+            i32::MAX
+        );
+        assert_eq!(
+            union.height,
+            i32::MAX
+        );
+        assert_eq!(
+            huge.intersection(
+                &Rect::new(
+                    i32::MAX - 1,
+                    0,
+                    1,
+                    1
+                )
+            ),
+            None
+        );
+    }
+}
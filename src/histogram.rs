@@ -0,0 +1,262 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    enums::BandFormat,
+    error::Error,
+    utils,
+    voption::{Setter, VOption},
+    Result, VipsImage,
+};
+
+/// Pixel counts per bin for each band of an image.
+///
+/// ```no_run
+/// use rs_vips::{Histogram, VipsImage};
+///
+/// let image = VipsImage::new_from_file("photo.jpg").unwrap();
+/// let histogram = Histogram::find(&image).unwrap();
+/// let (low, high) = (histogram.percentile(0, 1.0), histogram.percentile(0, 99.0));
+/// println!("1% at {}, 99% at {}, mean {}", low, high, histogram.mean(0));
+/// let equalized = image
+///     .maplut(&histogram.to_lut().unwrap())
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Histogram {
+    bands: Vec<Vec<u64>>,
+}
+
+impl Histogram {
+    /// Wraps counts, one `Vec` of bins per band.
+    pub fn new(bands: Vec<Vec<u64>>) -> Histogram {
+        Histogram {
+            bands,
+        }
+    }
+
+    /// Decodes a histogram image, such as the output of `hist_find`, `hist_cum` or `hist_norm`.
+    /// Bins are read in pixel order, so a `hist_find_ndim` image gives one band per bin of the third dimension.
+    pub fn from_image(image: &VipsImage) -> Result<Histogram> {
+        let n_bands = image.get_bands() as usize;
+        let values = utils::to_f64_vec(image)?;
+        if n_bands == 0 || values.len() % n_bands != 0 {
+            return Err(Error::OperationError("Invalid histogram image".to_string()));
+        }
+        let bands = (0..n_bands)
+            .map(|band| {
+                values
+                    .iter()
+                    .skip(band)
+                    .step_by(n_bands)
+                    .map(|value| {
+                        value
+                            .round()
+                            .max(0.0) as u64
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Histogram::new(bands))
+    }
+
+    /// Counts the pixels of every band with `hist_find`.
+    pub fn find(image: &VipsImage) -> Result<Histogram> {
+        Histogram::from_image(&image.hist_find()?)
+    }
+
+    /// Counts the pixels with `hist_find` options, such as `band`.
+    pub fn find_with_opts(image: &VipsImage, option: VOption) -> Result<Histogram> {
+        Histogram::from_image(&image.hist_find_with_opts(option)?)
+    }
+
+    /// Counts the pixels of a three-band image in `bins` bins per dimension with `hist_find_ndim`.
+    pub fn find_ndim(image: &VipsImage, bins: i32) -> Result<Histogram> {
+        Histogram::from_image(
+            &image.hist_find_ndim_with_opts(
+                VOption::new().set(
+                    "bins",
+                    bins,
+                ),
+            )?,
+        )
+    }
+
+    /// The cumulative histogram of the image, with `hist_find` and `hist_cum`
+    pub fn find_cumulative(image: &VipsImage) -> Result<Histogram> {
+        Histogram::from_image(
+            &image
+                .hist_find()?
+                .hist_cum()?,
+        )
+    }
+
+    /// The histogram of the image scaled by `hist_norm` so that the largest bin equals the largest bin index,
+    /// e.g. 255 for a `uchar` image
+    pub fn find_normalized(image: &VipsImage) -> Result<Histogram> {
+        Histogram::from_image(
+            &image
+                .hist_find()?
+                .hist_norm()?,
+        )
+    }
+
+    /// Bins of every band
+    pub fn bands(&self) -> &[Vec<u64>] {
+        &self.bands
+    }
+
+    pub fn n_bands(&self) -> usize {
+        self.bands
+            .len()
+    }
+
+    /// Bins of a band. Empty if the band does not exist.
+    pub fn bins(&self, band: usize) -> &[u64] {
+        self.bands
+            .get(band)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Number of pixels counted in a band
+    pub fn total(&self, band: usize) -> u64 {
+        self.bins(band)
+            .iter()
+            .sum()
+    }
+
+    /// The smallest bin below or at which `percent` of the pixels of the band fall
+    pub fn percentile(&self, band: usize, percent: f64) -> usize {
+        let bins = self.bins(band);
+        let threshold = self.total(band) as f64 * percent.clamp(0.0, 100.0) / 100.0;
+        let mut sum = 0;
+        for (bin, count) in bins
+            .iter()
+            .enumerate()
+        {
+            sum += count;
+            if sum as f64 >= threshold && sum > 0 {
+                return bin;
+            }
+        }
+        bins.len()
+            .saturating_sub(1)
+    }
+
+    /// The mean bin of a band
+    pub fn mean(&self, band: usize) -> f64 {
+        let total = self.total(band);
+        if total == 0 {
+            return 0.0;
+        }
+        self.bins(band)
+            .iter()
+            .enumerate()
+            .map(|(bin, count)| bin as f64 * *count as f64)
+            .sum::<f64>()
+            / total as f64
+    }
+
+    /// The Shannon entropy of a band in bits
+    pub fn entropy(&self, band: usize) -> f64 {
+        let total = self.total(band) as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        -self
+            .bins(band)
+            .iter()
+            .filter(|count| **count > 0)
+            .map(|count| {
+                let p = *count as f64 / total;
+                p * p.log2()
+            })
+            .sum::<f64>()
+    }
+
+    /// The running totals of the bins of each band, as `hist_cum`
+    pub fn cumulative(&self) -> Histogram {
+        Histogram::new(
+            self.bands
+                .iter()
+                .map(|bins| {
+                    bins.iter()
+                        .scan(
+                            0,
+                            |sum, count| {
+                                *sum += count;
+                                Some(*sum)
+                            },
+                        )
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// A lookup table for `maplut` which equalizes the histogram, mapping each bin to its cumulative share
+    /// of the pixels. The table is `uchar` for up to 256 bins and `ushort` otherwise.
+    pub fn to_lut(&self) -> Result<VipsImage> {
+        let n_bins = self
+            .bands
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+        if n_bins == 0 {
+            return Err(Error::OperationError("Histogram is empty".to_string()));
+        }
+        let max = (n_bins - 1) as f64;
+        let cumulative = self.cumulative();
+        let lut: Vec<Vec<f64>> = cumulative
+            .bands
+            .iter()
+            .map(|bins| {
+                let total = bins
+                    .last()
+                    .copied()
+                    .unwrap_or_default()
+                    .max(1) as f64;
+                (0..n_bins)
+                    .map(|bin| {
+                        let sum = bins
+                            .get(bin)
+                            .or(bins.last())
+                            .copied()
+                            .unwrap_or_default();
+                        (sum as f64 * max / total).round()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let (format, size) = if n_bins <= 256 {
+            (
+                BandFormat::Uchar,
+                1,
+            )
+        } else {
+            (
+                BandFormat::Ushort,
+                2,
+            )
+        };
+        let mut buffer = Vec::with_capacity(n_bins * lut.len() * size);
+        for bin in 0..n_bins {
+            for band in &lut {
+                let value = band[bin];
+                if size == 1 {
+                    buffer.push(value as u8);
+                } else {
+                    buffer.extend_from_slice(&(value as u16).to_ne_bytes());
+                }
+            }
+        }
+        VipsImage::new_from_memory_copy(
+            &buffer,
+            n_bins as i32,
+            1,
+            lut.len() as i32,
+            format,
+        )
+    }
+}
@@ -0,0 +1,570 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{enums::Intent, error::Error, Result, VipsImage};
+
+const ICC_PROFILE_DATA: &str = "icc-profile-data";
+const HEADER_SIZE: usize = 128;
+
+/// Colour space of the data or of the profile connection space of an ICC profile
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IccColorSpace {
+    Xyz,
+    Lab,
+    Luv,
+    YCbCr,
+    Yxy,
+    Rgb,
+    Gray,
+    Hsv,
+    Hls,
+    Cmyk,
+    Cmy,
+    /// Any other signature, e.g. `6CLR`
+    Other(String),
+}
+
+impl IccColorSpace {
+    fn from_signature(signature: &[u8]) -> IccColorSpace {
+        match signature {
+            b"XYZ " => IccColorSpace::Xyz,
+            b"Lab " => IccColorSpace::Lab,
+            b"Luv " => IccColorSpace::Luv,
+            b"YCbr" => IccColorSpace::YCbCr,
+            b"Yxy " => IccColorSpace::Yxy,
+            b"RGB " => IccColorSpace::Rgb,
+            b"GRAY" => IccColorSpace::Gray,
+            b"HSV " => IccColorSpace::Hsv,
+            b"HLS " => IccColorSpace::Hls,
+            b"CMYK" => IccColorSpace::Cmyk,
+            b"CMY " => IccColorSpace::Cmy,
+            _ => IccColorSpace::Other(signature_to_string(signature)),
+        }
+    }
+}
+
+/// Device class of an ICC profile
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IccDeviceClass {
+    /// Input devices such as scanners and cameras
+    Input,
+    Display,
+    /// Output devices such as printers
+    Output,
+    DeviceLink,
+    ColorSpace,
+    Abstract,
+    NamedColor,
+    /// Any other signature
+    Other(String),
+}
+
+impl IccDeviceClass {
+    fn from_signature(signature: &[u8]) -> IccDeviceClass {
+        match signature {
+            b"scnr" => IccDeviceClass::Input,
+            b"mntr" => IccDeviceClass::Display,
+            b"prtr" => IccDeviceClass::Output,
+            b"link" => IccDeviceClass::DeviceLink,
+            b"spac" => IccDeviceClass::ColorSpace,
+            b"abst" => IccDeviceClass::Abstract,
+            b"nmcl" => IccDeviceClass::NamedColor,
+            _ => IccDeviceClass::Other(signature_to_string(signature)),
+        }
+    }
+}
+
+fn signature_to_string(signature: &[u8]) -> String {
+    String::from_utf8_lossy(signature)
+        .trim_end()
+        .to_string()
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| {
+            u32::from_be_bytes(
+                bytes
+                    .try_into()
+                    .unwrap(),
+            )
+        })
+}
+
+/// An ICC profile, such as the `icc-profile-data` item attached to an image
+#[derive(Clone, Debug, PartialEq)]
+pub struct IccProfile {
+    data: Vec<u8>,
+}
+
+impl IccProfile {
+    /// Wraps the profile data after checking its header.
+    pub fn new(data: Vec<u8>) -> Result<IccProfile> {
+        if data.len() < HEADER_SIZE + 4 || &data[36..40] != b"acsp" {
+            return Err(Error::OperationError("Invalid ICC profile".to_string()));
+        }
+        Ok(
+            IccProfile {
+                data,
+            },
+        )
+    }
+
+    /// Loads one of the built-in profiles of libvips, `srgb`, `cmyk` or `p3`, or a profile file.
+    pub fn load(name: &str) -> Result<IccProfile> {
+        IccProfile::new(VipsImage::profile_load(name)?)
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Profile version as (major, minor), e.g. (4, 3)
+    pub fn version(&self) -> (u8, u8) {
+        (
+            self.data[8],
+            self.data[9] >> 4,
+        )
+    }
+
+    pub fn device_class(&self) -> IccDeviceClass {
+        IccDeviceClass::from_signature(&self.data[12..16])
+    }
+
+    /// Colour space of the data the profile applies to
+    pub fn color_space(&self) -> IccColorSpace {
+        IccColorSpace::from_signature(&self.data[16..20])
+    }
+
+    /// Profile connection space, XYZ or Lab
+    pub fn connection_space(&self) -> IccColorSpace {
+        IccColorSpace::from_signature(&self.data[20..24])
+    }
+
+    /// Default rendering intent recorded in the header
+    pub fn rendering_intent(&self) -> Intent {
+        match read_u32(
+            &self.data,
+            64,
+        ) {
+            Some(1) => Intent::Relative,
+            Some(2) => Intent::Saturation,
+            Some(3) => Intent::Absolute,
+            _ => Intent::Perceptual,
+        }
+    }
+
+    /// Rendering intents the profile provides transforms for.
+    /// Matrix/TRC profiles serve every intent with the same colorimetric transform.
+    pub fn rendering_intents(&self) -> Vec<Intent> {
+        let has = |signatures: &[&[u8; 4]]| {
+            signatures
+                .iter()
+                .any(
+                    |signature| {
+                        self.tag(signature)
+                            .is_some()
+                    },
+                )
+        };
+        let mut intents = Vec::new();
+        if has(&[b"A2B0", b"B2A0"]) {
+            intents.push(Intent::Perceptual);
+        }
+        if has(&[b"A2B1", b"B2A1"]) {
+            intents.push(Intent::Relative);
+            intents.push(Intent::Absolute);
+        }
+        if has(&[b"A2B2", b"B2A2"]) {
+            intents.push(Intent::Saturation);
+        }
+        if intents.is_empty() && (has(&[b"kTRC"]) || has(&[b"rXYZ"]) && has(&[b"rTRC"])) {
+            intents = vec![
+                Intent::Perceptual,
+                Intent::Relative,
+                Intent::Saturation,
+                Intent::Absolute,
+            ];
+        }
+        intents
+    }
+
+    /// Profile description from the `desc` tag, e.g. `sRGB IEC61966-2.1`
+    pub fn description(&self) -> Option<String> {
+        self.text(b"desc")
+    }
+
+    /// Copyright from the `cprt` tag
+    pub fn copyright(&self) -> Option<String> {
+        self.text(b"cprt")
+    }
+
+    /// Returns the data of the tag with the signature.
+    pub fn tag(&self, signature: &[u8; 4]) -> Option<&[u8]> {
+        // The count is not trusted beyond the entries which fit in the data
+        let count = (read_u32(
+            &self.data,
+            HEADER_SIZE,
+        )? as usize)
+            .min(
+                self.data
+                    .len()
+                    .saturating_sub(HEADER_SIZE + 4)
+                    / 12,
+            );
+        (0..count).find_map(|i| {
+            let entry = HEADER_SIZE + 4 + i * 12;
+            if self
+                .data
+                .get(entry..entry + 4)?
+                != signature
+            {
+                return None;
+            }
+            let offset = read_u32(
+                &self.data,
+                entry + 4,
+            )? as usize;
+            let size = read_u32(
+                &self.data,
+                entry + 8,
+            )? as usize;
+            self.data
+                .get(offset..offset.checked_add(size)?)
+        })
+    }
+
+    /// Decodes a `desc`, `text` or `mluc` tag. For `mluc`, the English record is preferred.
+    fn text(&self, signature: &[u8; 4]) -> Option<String> {
+        let tag = self.tag(signature)?;
+        let text = match tag.get(..4)? {
+            b"desc" => {
+                let count = read_u32(tag, 8)? as usize;
+                String::from_utf8_lossy(tag.get(12..12usize.checked_add(count)?)?).to_string()
+            }
+            b"text" => String::from_utf8_lossy(tag.get(8..)?).to_string(),
+            b"mluc" => {
+                let record_size = read_u32(tag, 12)? as usize;
+                if record_size < 12 {
+                    return None;
+                }
+                let records = (read_u32(tag, 8)? as usize).min(
+                    tag.len()
+                        .saturating_sub(16)
+                        / record_size,
+                );
+                let record = (0..records)
+                    .map(|i| 16 + i * record_size)
+                    .find(|record| tag.get(*record..*record + 2) == Some(b"en"))
+                    .unwrap_or(16);
+                let len = read_u32(
+                    tag,
+                    record + 4,
+                )? as usize;
+                let offset = read_u32(
+                    tag,
+                    record + 8,
+                )? as usize;
+                let units: Vec<u16> = tag
+                    .get(offset..offset.checked_add(len)?)?
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            _ => return None,
+        };
+        Some(
+            text.trim_end_matches('\0')
+                .to_string(),
+        )
+    }
+}
+
+impl VipsImage {
+    /// Returns the attached `icc-profile-data` item.
+    pub fn icc_profile(&self) -> Result<IccProfile> {
+        IccProfile::new(self.get_blob(ICC_PROFILE_DATA)?)
+    }
+
+    /// Returns a copy of the image with the profile attached as `icc-profile-data`.
+    /// The profile data is checked, but not whether it matches the interpretation of the image.
+    pub fn with_icc_profile(&self, profile: &[u8]) -> Result<VipsImage> {
+        let profile = IccProfile::new(profile.to_vec())?;
+        let mut out = self.copy()?;
+        out.set_blob_copy(
+            ICC_PROFILE_DATA,
+            profile.data(),
+        )?;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a profile with a valid header and the tags in order, each followed by its data.
+    fn build(
+        tags: &[(
+            &[u8; 4],
+            Vec<u8>,
+        )],
+    ) -> Vec<u8> {
+        let mut data = vec![0u8; HEADER_SIZE];
+        data[36..40].copy_from_slice(b"acsp");
+        data.extend_from_slice(&(tags.len() as u32).to_be_bytes());
+        let mut offset = HEADER_SIZE + 4 + tags.len() * 12;
+        for (signature, tag) in tags {
+            data.extend_from_slice(*signature);
+            data.extend_from_slice(&(offset as u32).to_be_bytes());
+            data.extend_from_slice(&(tag.len() as u32).to_be_bytes());
+            offset += tag.len();
+        }
+        for (_, tag) in tags {
+            data.extend_from_slice(tag);
+        }
+        data
+    }
+
+    fn desc(text: &str) -> Vec<u8> {
+        let mut tag = b"desc\0\0\0\0".to_vec();
+        tag.extend_from_slice(&(text.len() as u32 + 1).to_be_bytes());
+        tag.extend_from_slice(text.as_bytes());
+        tag.push(0);
+        tag
+    }
+
+    fn mluc(
+        records: &[(
+            &[u8; 4],
+            &str,
+        )],
+    ) -> Vec<u8> {
+        let mut tag = b"mluc\0\0\0\0".to_vec();
+        tag.extend_from_slice(&(records.len() as u32).to_be_bytes());
+        tag.extend_from_slice(&12u32.to_be_bytes());
+        let mut strings = Vec::new();
+        for (language, text) in records {
+            let units: Vec<u8> = text
+                .encode_utf16()
+                .flat_map(|unit| unit.to_be_bytes())
+                .collect();
+            tag.extend_from_slice(*language);
+            tag.extend_from_slice(&(units.len() as u32).to_be_bytes());
+            tag.extend_from_slice(
+                &((16 + records.len() * 12 + strings.len()) as u32).to_be_bytes(),
+            );
+            strings.extend_from_slice(&units);
+        }
+        tag.extend_from_slice(&strings);
+        tag
+    }
+
+    #[test]
+    fn description_from_desc() {
+        let profile = IccProfile::new(build(&[
+            (
+                b"desc",
+                desc("sRGB IEC61966-2.1"),
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(
+            profile.description(),
+            Some("sRGB IEC61966-2.1".to_string())
+        );
+        assert_eq!(
+            profile.copyright(),
+            None
+        );
+    }
+
+    #[test]
+    fn mluc_prefers_english() {
+        let profile = IccProfile::new(build(&[
+            (
+                b"desc",
+                mluc(&[
+                    (
+                        b"frFR",
+                        "Profil",
+                    ),
+                    (
+                        b"enUS",
+                        "Profile",
+                    ),
+                ]),
+            ),
+            (
+                b"cprt",
+                mluc(&[
+                    (
+                        b"deDE",
+                        "Urheberrecht",
+                    ),
+                    (
+                        b"jaJP",
+                        "著作権",
+                    ),
+                ]),
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(
+            profile.description(),
+            Some("Profile".to_string())
+        );
+        // Without an English record, the first one is used
+        assert_eq!(
+            profile.copyright(),
+            Some("Urheberrecht".to_string())
+        );
+    }
+
+    #[test]
+    fn mluc_with_bad_records() {
+        let mut small_records = mluc(&[(
+            b"enUS",
+            "Profile",
+        )]);
+        small_records[12..16].copy_from_slice(&4u32.to_be_bytes());
+        let mut many_records = mluc(&[(
+            b"enUS",
+            "Profile",
+        )]);
+        many_records[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+        let profile = IccProfile::new(build(&[
+            (
+                b"desc",
+                small_records,
+            ),
+            (
+                b"cprt",
+                many_records,
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(
+            profile.description(),
+            None
+        );
+        assert_eq!(
+            profile.copyright(),
+            Some("Profile".to_string())
+        );
+    }
+
+    #[test]
+    fn truncated_tag_table() {
+        let mut data = build(&[
+            (
+                b"desc",
+                desc("first"),
+            ),
+            (
+                b"cprt",
+                desc("second"),
+            ),
+        ]);
+        // Keep the first entry whole and cut the second one short
+        data.truncate(HEADER_SIZE + 4 + 12 + 6);
+        let profile = IccProfile::new(data).unwrap();
+        // The data of the first tag is gone as well
+        assert_eq!(
+            profile.tag(b"desc"),
+            None
+        );
+        assert_eq!(
+            profile.tag(b"cprt"),
+            None
+        );
+        assert_eq!(
+            profile.description(),
+            None
+        );
+        assert!(IccProfile::new(vec![0; HEADER_SIZE]).is_err());
+    }
+
+    #[test]
+    fn oversized_tag_table() {
+        let mut data = build(&[(
+            b"desc",
+            desc("sRGB"),
+        )]);
+        data[HEADER_SIZE..HEADER_SIZE + 4].copy_from_slice(&u32::MAX.to_be_bytes());
+        let profile = IccProfile::new(data).unwrap();
+        assert_eq!(
+            profile.description(),
+            Some("sRGB".to_string())
+        );
+        assert_eq!(
+            profile.tag(b"A2B0"),
+            None
+        );
+
+        // A tag pointing past the end of the data is not returned
+        let mut data = build(&[(
+            b"desc",
+            desc("sRGB"),
+        )]);
+        data[HEADER_SIZE + 12..HEADER_SIZE + 16].copy_from_slice(&u32::MAX.to_be_bytes());
+        let profile = IccProfile::new(data).unwrap();
+        assert_eq!(
+            profile.tag(b"desc"),
+            None
+        );
+    }
+
+    #[test]
+    fn rendering_intents() {
+        let lut = IccProfile::new(build(&[
+            (
+                b"A2B0",
+                vec![0; 4],
+            ),
+            (
+                b"B2A1",
+                vec![0; 4],
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(
+            lut.rendering_intents(),
+            vec![Intent::Perceptual, Intent::Relative, Intent::Absolute]
+        );
+
+        let matrix = IccProfile::new(build(&[
+            (
+                b"rXYZ",
+                vec![0; 4],
+            ),
+            (
+                b"rTRC",
+                vec![0; 4],
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(
+            matrix.rendering_intents(),
+            vec![
+                Intent::Perceptual,
+                Intent::Relative,
+                Intent::Saturation,
+                Intent::Absolute
+            ]
+        );
+
+        let empty = IccProfile::new(build(&[])).unwrap();
+        assert_eq!(
+            empty.rendering_intents(),
+            Vec::new()
+        );
+        assert_eq!(
+            empty.rendering_intent(),
+            Intent::Perceptual
+        );
+    }
+}
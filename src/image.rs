@@ -1,12 +1,12 @@
 // (c) Copyright 2019-2025 OLX
 // (c) Copyright 2025 mrdkprj
 use crate::{
-    bindings,
+    bindings, context,
     enums::*,
     error::Error,
     utils::{
-        self, ensure_null_terminated, new_c_string, new_c_string_from_raw, new_vipsimage,
-        path_to_cstring, vips_image_result, vips_image_result_ext,
+        self, ensure_null_terminated, find_blocked_loader, new_c_string, new_c_string_from_raw,
+        new_vipsimage, path_to_cstring, vips_image_result, vips_image_result_ext, LoadInput,
     },
     voption::{call, call_option_string_, Setter, VOption},
     Result, VipsSource, VipsTarget,
@@ -62,14 +62,7 @@ pub(crate) struct Image {
 
 impl Drop for Image {
     fn drop(&mut self) {
-        unsafe {
-            if !self
-                .ctx
-                .is_null()
-            {
-                bindings::g_object_unref(self.ctx as *mut c_void);
-            }
-        }
+        context::unref(self.ctx);
     }
 }
 
@@ -88,6 +81,7 @@ impl VipsImage {
 
     /// Creates a new `VipsImage` which, when written to, will create a memory image.
     pub fn new_memory() -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let res = bindings::vips_image_new_memory();
             vips_image_result(
@@ -110,6 +104,7 @@ impl VipsImage {
         filename: P,
         option: VOption,
     ) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let filename_c_str = path_to_cstring(filename)?;
             let filename_part = bindings::vips_filename_get_filename(filename_c_str.as_ptr());
@@ -117,6 +112,14 @@ impl VipsImage {
 
             let operation = bindings::vips_foreign_find_load(filename_part);
             if operation.is_null() {
+                if let Some(loader) = find_blocked_loader(LoadInput::File(filename_part)) {
+                    return Err(
+                        Error::Blocked(format!(
+                            "Loader {} is blocked",
+                            loader
+                        )),
+                    );
+                }
                 return vips_image_result(
                     NULL as _,
                     Error::InitializationError("Could not find operation".to_string()),
@@ -147,6 +150,7 @@ impl VipsImage {
 
     /// Opens the named file for simultaneous reading and writing.
     pub fn new_from_file_rw<P: AsRef<Path>>(filename: P) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let filename_c_str = path_to_cstring(filename)?;
             let res = bindings::vips_image_new_from_file_RW(filename_c_str.as_ptr());
@@ -165,6 +169,7 @@ impl VipsImage {
         bands: i32,
         offset: u64,
     ) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let filename_c_str = path_to_cstring(filename)?;
             let res = bindings::vips_image_new_from_file_raw(
@@ -198,12 +203,21 @@ impl VipsImage {
         option_str: &str,
         option: VOption,
     ) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let operation = bindings::vips_foreign_find_load_buffer(
                 buffer.as_ptr() as *const c_void,
                 buffer.len(),
             );
             if operation.is_null() {
+                if let Some(loader) = find_blocked_loader(LoadInput::Buffer(buffer)) {
+                    return Err(
+                        Error::Blocked(format!(
+                            "Loader {} is blocked",
+                            loader
+                        )),
+                    );
+                }
                 return vips_image_result(
                     NULL as _,
                     Error::InitializationError(
@@ -250,9 +264,18 @@ impl VipsImage {
         option_str: &str,
         option: VOption,
     ) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let operation = bindings::vips_foreign_find_load_source(source.ctx);
             if operation.is_null() {
+                if let Some(loader) = find_blocked_loader(LoadInput::Source(source.ctx)) {
+                    return Err(
+                        Error::Blocked(format!(
+                            "Loader {} is blocked",
+                            loader
+                        )),
+                    );
+                }
                 return vips_image_result(
                     NULL as _,
                     Error::InitializationError(
@@ -298,6 +321,7 @@ impl VipsImage {
         bands: i32,
         format: BandFormat,
     ) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             if let Some(format) = format.to_i32() {
                 let res = bindings::vips_image_new_from_memory(
@@ -330,6 +354,7 @@ impl VipsImage {
         bands: i32,
         format: BandFormat,
     ) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             if let Some(format) = format.to_i32() {
                 let res = bindings::vips_image_new_from_memory_copy(
@@ -356,6 +381,7 @@ impl VipsImage {
 
     /// Makes an image which is a matrix: a one-band `VIPS_FORMAT_DOUBLE` image held in memory.
     pub fn new_matrix(width: i32, height: i32) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let res = bindings::vips_image_new_matrix(
                 width,
@@ -381,6 +407,7 @@ impl VipsImage {
 
     /// As `new_matrix()`, but initialise the matrix from the argument list. Same as `new_matrixv()`.
     pub fn new_matrix_from_array(width: i32, height: i32, array: &[f64]) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let res = bindings::vips_image_new_matrix_from_array(
                 width,
@@ -437,6 +464,7 @@ impl VipsImage {
 
     /// Makes a `VipsImage` which, when written to, will create a temporary file on disc. The file will be automatically deleted when the image is destroyed.
     pub fn new_temp_file(format: &str) -> Result<VipsImage> {
+        let _guard = context::enter()?;
         unsafe {
             let format_c_str = new_c_string(format)?;
             let res = bindings::vips_image_new_temp_file(format_c_str.as_ptr());
@@ -461,6 +489,11 @@ impl VipsImage {
 
     /// Allocates memory, renders image into it, builds a new image around the memory area, and returns that.
     pub fn copy_memory(&self) -> Result<VipsImage> {
+        #[cfg(feature = "tracing")]
+        let _span = crate::trace::OperationSpan::evaluation(
+            "copy_memory",
+            self,
+        );
         unsafe {
             let result = bindings::vips_image_copy_memory(
                 self.image
@@ -791,6 +824,11 @@ impl VipsImage {
 
     /// Writes this image to another image.
     pub fn write(&self) -> Result<VipsImage> {
+        #[cfg(feature = "tracing")]
+        let _span = crate::trace::OperationSpan::evaluation(
+            "write",
+            self,
+        );
         unsafe {
             let out = bindings::vips_image_new();
             let res = bindings::vips_image_write(
@@ -826,6 +864,7 @@ impl VipsImage {
         filename: P,
         option: VOption,
     ) -> Result<()> {
+        let _guard = context::enter()?;
         unsafe {
             let filename_c_str = path_to_cstring(filename)?;
             let filename_part = bindings::vips_filename_get_filename(filename_c_str.as_ptr());
@@ -868,6 +907,7 @@ impl VipsImage {
 
     /// Writes this image to memory.
     pub fn write_to_buffer_with_opts(&self, suffix: &str, option: VOption) -> Result<Vec<u8>> {
+        let _guard = context::enter()?;
         unsafe {
             let suffix_c_str = new_c_string(suffix)?;
             let filename_part = bindings::vips_filename_get_filename(suffix_c_str.as_ptr());
@@ -946,6 +986,7 @@ impl VipsImage {
         target: &VipsTarget,
         option: VOption,
     ) -> Result<()> {
+        let _guard = context::enter()?;
         unsafe {
             let suffix_c_str = new_c_string(suffix)?;
             let string_options = bindings::vips_filename_get_options(suffix_c_str.as_ptr());
@@ -980,6 +1021,11 @@ impl VipsImage {
 
     /// Writes this image to a large memory array.
     pub fn write_to_memory(&self) -> Vec<u8> {
+        #[cfg(feature = "tracing")]
+        let _span = crate::trace::OperationSpan::evaluation(
+            "write_to_memory",
+            self,
+        );
         unsafe {
             let mut buffer_buf_size = 0;
             let buffer_out = bindings::vips_image_write_to_memory(
@@ -1341,6 +1387,27 @@ impl VipsImage {
         }
     }
 
+    /// Gets image from image under the name.
+    pub fn get_image(&self, name: impl AsRef<[u8]>) -> Result<VipsImage> {
+        unsafe {
+            let mut out: *mut bindings::VipsImage = null_mut();
+            let name = ensure_null_terminated(name)?;
+            let res = bindings::vips_image_get_image(
+                self.image
+                    .ctx,
+                name.as_ptr(),
+                &mut out,
+            );
+            utils::result(
+                res,
+                new_vipsimage(
+                    out, None, None,
+                ),
+                Error::IOError("Cannot get image".to_string()),
+            )
+        }
+    }
+
     /// Attaches image as a metadata item on image as name.
     pub fn set_image(&mut self, name: impl AsRef<[u8]>, value: &VipsImage) -> Result<()> {
         unsafe {
@@ -1,12 +1,11 @@
 // (c) Copyright 2019-2025 OLX
 // (c) Copyright 2025 mrdkprj
 use crate::{
-    bindings,
+    bindings, context,
     error::Error,
     utils::{new_c_string, result_cond},
     Result,
 };
-use std::ffi::c_void;
 
 /// Base struct for the various interpolation functions
 #[derive(Debug, Clone)]
@@ -17,6 +16,7 @@ pub struct VipsInterpolate {
 impl VipsInterpolate {
     /// Look up an interpolator from a nickname and make one.
     pub fn new_from_name(name: &str) -> Result<VipsInterpolate> {
+        let _guard = context::enter()?;
         unsafe {
             let nickname = new_c_string(name)?;
             let res = bindings::vips_interpolate_new(nickname.as_ptr());
@@ -63,13 +63,6 @@ impl VipsInterpolate {
 
 impl Drop for VipsInterpolate {
     fn drop(&mut self) {
-        unsafe {
-            if !self
-                .ctx
-                .is_null()
-            {
-                bindings::g_object_unref(self.ctx as *mut c_void);
-            }
-        }
+        context::unref(self.ctx);
     }
 }
@@ -0,0 +1,624 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{error::Error, Result, VipsImage};
+
+pub(crate) const IPTC_DATA: &str = "iptc-data";
+const PHOTOSHOP_HEADER: &[u8] = b"Photoshop 3.0\0";
+const RESOURCE_SIGNATURE: &[u8] = b"8BIM";
+const IPTC_RESOURCE_ID: u16 = 0x0404;
+const TAG_MARKER: u8 = 0x1C;
+
+/// UTF-8 designation for the Coded Character Set dataset
+const UTF8: &[u8] = b"\x1b%G";
+
+/// A dataset of an IPTC-IIM stream
+#[derive(Clone, Debug, PartialEq)]
+pub struct IptcDataSet {
+    pub record: u8,
+    pub dataset: u8,
+    pub data: Vec<u8>,
+}
+
+/// IPTC-IIM datasets of an `iptc-data` item.
+/// JPEG images carry them in a Photoshop `8BIM` resource block whose other resources are kept on write.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Iptc {
+    datasets: Vec<IptcDataSet>,
+    /// The Photoshop resource block the datasets were read from, without the IPTC resource
+    resources: Option<Vec<Resource>>,
+}
+
+/// A Photoshop image resource other than IPTC, e.g. the thumbnail or the caption digest
+#[derive(Clone, Debug, PartialEq)]
+struct Resource {
+    id: u16,
+    name: Vec<u8>,
+    data: Vec<u8>,
+}
+
+fn pad_even(len: usize) -> usize {
+    len + len % 2
+}
+
+impl Iptc {
+    /// 1:90 Coded Character Set
+    pub const CODED_CHARACTER_SET: (u8, u8) = (1, 90);
+    /// 2:00 Record Version
+    pub const RECORD_VERSION: (u8, u8) = (2, 0);
+    /// 2:05 Object Name
+    pub const OBJECT_NAME: (u8, u8) = (2, 5);
+    /// 2:25 Keywords
+    pub const KEYWORDS: (u8, u8) = (2, 25);
+    /// 2:55 Date Created
+    pub const DATE_CREATED: (u8, u8) = (2, 55);
+    /// 2:80 By-line, the creator
+    pub const BY_LINE: (u8, u8) = (2, 80);
+    /// 2:85 By-line Title
+    pub const BY_LINE_TITLE: (u8, u8) = (2, 85);
+    /// 2:90 City
+    pub const CITY: (u8, u8) = (2, 90);
+    /// 2:101 Country Name
+    pub const COUNTRY: (u8, u8) = (2, 101);
+    /// 2:105 Headline
+    pub const HEADLINE: (u8, u8) = (2, 105);
+    /// 2:110 Credit
+    pub const CREDIT: (u8, u8) = (2, 110);
+    /// 2:115 Source
+    pub const SOURCE: (u8, u8) = (2, 115);
+    /// 2:116 Copyright Notice
+    pub const COPYRIGHT_NOTICE: (u8, u8) = (2, 116);
+    /// 2:120 Caption/Abstract
+    pub const CAPTION: (u8, u8) = (2, 120);
+
+    pub fn new() -> Iptc {
+        Iptc::default()
+    }
+
+    /// Parses either a Photoshop resource block as stored by JPEG, or a raw IIM stream as stored by TIFF.
+    pub fn parse(data: &[u8]) -> Result<Iptc> {
+        let Some(mut rest) = data.strip_prefix(PHOTOSHOP_HEADER) else {
+            return Ok(Iptc {
+                datasets: parse_iim(data)?,
+                resources: None,
+            });
+        };
+
+        let invalid = || Error::IOError("Invalid Photoshop resource block".to_string());
+        let mut datasets = Vec::new();
+        let mut resources = Vec::new();
+        while rest.len() >= 4 && rest.starts_with(RESOURCE_SIGNATURE) {
+            let id = u16::from_be_bytes([
+                *rest
+                    .get(4)
+                    .ok_or_else(invalid)?,
+                *rest
+                    .get(5)
+                    .ok_or_else(invalid)?,
+            ]);
+            let name_len = *rest
+                .get(6)
+                .ok_or_else(invalid)? as usize;
+            // The Pascal string, including its length byte, is padded to an even size
+            let name_end = 6 + pad_even(name_len + 1);
+            let name = rest
+                .get(7..7 + name_len)
+                .ok_or_else(invalid)?;
+            let size = u32::from_be_bytes(
+                rest.get(name_end..name_end + 4)
+                    .ok_or_else(invalid)?
+                    .try_into()
+                    .unwrap(),
+            ) as usize;
+            let body = rest
+                .get(name_end + 4..name_end + 4 + size)
+                .ok_or_else(invalid)?;
+            if id == IPTC_RESOURCE_ID {
+                datasets = parse_iim(body)?;
+            } else {
+                resources.push(Resource {
+                    id,
+                    name: name.to_vec(),
+                    data: body.to_vec(),
+                });
+            }
+            rest = rest
+                .get(name_end + 4 + pad_even(size)..)
+                .unwrap_or_default();
+        }
+
+        Ok(Iptc {
+            datasets,
+            resources: Some(resources),
+        })
+    }
+
+    pub fn datasets(&self) -> &[IptcDataSet] {
+        &self.datasets
+    }
+
+    /// Returns the first value of the dataset as text, e.g. `get(Iptc::COPYRIGHT_NOTICE)`.
+    pub fn get(&self, (record, dataset): (u8, u8)) -> Option<String> {
+        self.get_all((
+            record,
+            dataset,
+        ))
+        .into_iter()
+        .next()
+    }
+
+    /// Returns every value of a repeatable dataset such as keywords.
+    pub fn get_all(&self, (record, dataset): (u8, u8)) -> Vec<String> {
+        self.datasets
+            .iter()
+            .filter(|set| set.record == record && set.dataset == dataset)
+            .map(|set| String::from_utf8_lossy(&set.data).to_string())
+            .collect()
+    }
+
+    /// Replaces the values of the dataset. Text is stored as UTF-8, which is declared in 1:90 when needed.
+    /// If 1:90 declares another character set, it is changed to UTF-8 when the other text of record 2 is ASCII,
+    /// and an error is returned otherwise, as that text would be misread.
+    pub fn set(&mut self, (record, dataset): (u8, u8), values: &[&str]) -> Result<()> {
+        let declare_utf8 = record == 2
+            && !values
+                .iter()
+                .all(|value| value.is_ascii());
+        let charset = self
+            .datasets
+            .iter()
+            .position(|set| {
+                (
+                    set.record,
+                    set.dataset,
+                ) == Iptc::CODED_CHARACTER_SET
+            });
+        if let (true, Some(charset)) = (
+            declare_utf8,
+            charset,
+        ) {
+            if self.datasets[charset].data != UTF8
+                && self
+                    .datasets
+                    .iter()
+                    .any(|set| {
+                        set.record == 2
+                            && set.dataset != dataset
+                            && !set
+                                .data
+                                .is_ascii()
+                    })
+            {
+                return Err(
+                    Error::OperationError(
+                        "IPTC text is in another character set than UTF-8".to_string(),
+                    ),
+                );
+            }
+            self.datasets[charset].data = UTF8.to_vec();
+        }
+
+        self.remove((
+            record,
+            dataset,
+        ));
+        let position = self
+            .datasets
+            .iter()
+            .position(|set| {
+                (
+                    set.record,
+                    set.dataset,
+                ) > (
+                    record,
+                    dataset,
+                )
+            })
+            .unwrap_or(
+                self.datasets
+                    .len(),
+            );
+        self.datasets
+            .splice(
+                position..position,
+                values
+                    .iter()
+                    .map(
+                        |value| IptcDataSet {
+                            record,
+                            dataset,
+                            data: value
+                                .as_bytes()
+                                .to_vec(),
+                        },
+                    ),
+            );
+
+        if declare_utf8 && charset.is_none() {
+            self.datasets
+                .insert(
+                    0,
+                    IptcDataSet {
+                        record: Iptc::CODED_CHARACTER_SET.0,
+                        dataset: Iptc::CODED_CHARACTER_SET.1,
+                        data: UTF8.to_vec(),
+                    },
+                );
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, (record, dataset): (u8, u8)) {
+        self.datasets
+            .retain(|set| set.record != record || set.dataset != dataset);
+    }
+
+    /// Sets the creators, 2:80 By-line.
+    pub fn with_creator(mut self, creators: &[&str]) -> Result<Iptc> {
+        self.set(
+            Iptc::BY_LINE,
+            creators,
+        )?;
+        Ok(self)
+    }
+
+    /// Sets the copyright notice, 2:116.
+    pub fn with_copyright(mut self, copyright: &str) -> Result<Iptc> {
+        self.set(
+            Iptc::COPYRIGHT_NOTICE,
+            &[copyright],
+        )?;
+        Ok(self)
+    }
+
+    /// Sets the credit line, 2:110.
+    pub fn with_credit(mut self, credit: &str) -> Result<Iptc> {
+        self.set(
+            Iptc::CREDIT,
+            &[credit],
+        )?;
+        Ok(self)
+    }
+
+    /// Serializes the datasets as a raw IIM stream, as TIFF stores them.
+    pub fn to_iim(&self) -> Vec<u8> {
+        let mut datasets = self
+            .datasets
+            .clone();
+        if !datasets.is_empty()
+            && !datasets
+                .iter()
+                .any(|set| {
+                    (
+                        set.record,
+                        set.dataset,
+                    ) == Iptc::RECORD_VERSION
+                })
+        {
+            let position = datasets
+                .iter()
+                .position(|set| set.record >= 2)
+                .unwrap_or(datasets.len());
+            datasets.insert(
+                position,
+                IptcDataSet {
+                    record: Iptc::RECORD_VERSION.0,
+                    dataset: Iptc::RECORD_VERSION.1,
+                    data: vec![0, 4],
+                },
+            );
+        }
+
+        let mut out = Vec::new();
+        for set in &datasets {
+            out.extend([TAG_MARKER, set.record, set.dataset]);
+            if set
+                .data
+                .len()
+                < 0x8000
+            {
+                out.extend(
+                    (set.data
+                        .len() as u16)
+                        .to_be_bytes(),
+                );
+            } else {
+                // Extended dataset: the high bit flags the count of length bytes that follow
+                out.extend(0x8004u16.to_be_bytes());
+                out.extend(
+                    (set.data
+                        .len() as u32)
+                        .to_be_bytes(),
+                );
+            }
+            out.extend(&set.data);
+        }
+        out
+    }
+
+    /// Serializes the datasets in the layout they were read from. New data uses the Photoshop resource block of JPEG.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let Some(resources) = &self.resources else {
+            if self
+                .datasets
+                .is_empty()
+            {
+                return Vec::new();
+            }
+            return Iptc {
+                datasets: self
+                    .datasets
+                    .clone(),
+                resources: Some(Vec::new()),
+            }
+            .to_bytes();
+        };
+
+        let iim = self.to_iim();
+        if iim.is_empty() && resources.is_empty() {
+            return Vec::new();
+        }
+        let mut out = PHOTOSHOP_HEADER.to_vec();
+        let iptc_resource = (!iim.is_empty()).then_some(Resource {
+            id: IPTC_RESOURCE_ID,
+            name: Vec::new(),
+            data: iim,
+        });
+        for resource in iptc_resource
+            .iter()
+            .chain(resources)
+        {
+            out.extend(RESOURCE_SIGNATURE);
+            out.extend(
+                resource
+                    .id
+                    .to_be_bytes(),
+            );
+            out.push(
+                resource
+                    .name
+                    .len() as u8,
+            );
+            out.extend(&resource.name);
+            if (resource
+                .name
+                .len()
+                + 1)
+                % 2
+                == 1
+            {
+                out.push(0);
+            }
+            out.extend(
+                (resource
+                    .data
+                    .len() as u32)
+                    .to_be_bytes(),
+            );
+            out.extend(&resource.data);
+            if resource
+                .data
+                .len()
+                % 2
+                == 1
+            {
+                out.push(0);
+            }
+        }
+        out
+    }
+}
+
+fn parse_iim(data: &[u8]) -> Result<Vec<IptcDataSet>> {
+    let invalid = || Error::IOError("Invalid IPTC data".to_string());
+    let mut datasets = Vec::new();
+    let mut pos = 0;
+    // Some writers pad the stream with zeros
+    while pos < data.len() && data[pos] == TAG_MARKER {
+        let header = data
+            .get(pos..pos + 5)
+            .ok_or_else(invalid)?;
+        let mut len = u16::from_be_bytes([header[3], header[4]]) as usize;
+        pos += 5;
+        if len & 0x8000 != 0 {
+            let count = len & 0x7FFF;
+            if count > 4 {
+                return Err(invalid());
+            }
+            len = data
+                .get(pos..pos + count)
+                .ok_or_else(invalid)?
+                .iter()
+                .fold(
+                    0,
+                    |len, byte| len << 8 | *byte as usize,
+                );
+            pos += count;
+        }
+        datasets.push(
+            IptcDataSet {
+                record: header[1],
+                dataset: header[2],
+                data: data
+                    .get(pos..pos + len)
+                    .ok_or_else(invalid)?
+                    .to_vec(),
+            },
+        );
+        pos += len;
+    }
+    Ok(datasets)
+}
+
+impl VipsImage {
+    /// Returns the datasets of the `iptc-data` item.
+    pub fn iptc(&self) -> Result<Iptc> {
+        Iptc::parse(&self.get_blob(IPTC_DATA)?)
+    }
+
+    /// Replaces the `iptc-data` item, or removes it when there are no datasets left.
+    pub fn set_iptc(&mut self, iptc: &Iptc) -> Result<()> {
+        let data = iptc.to_bytes();
+        if data.is_empty() {
+            self.remove(IPTC_DATA)?;
+            Ok(())
+        } else {
+            self.set_blob_copy(
+                IPTC_DATA,
+                &data,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Iptc {
+        Iptc::new()
+            .with_creator(&["Jane", "John"])
+            .unwrap()
+            .with_copyright("© 2024 Jane")
+            .unwrap()
+            .with_credit("Agency")
+            .unwrap()
+    }
+
+    #[test]
+    fn set_declares_utf8() {
+        let iptc = sample();
+        assert_eq!(
+            iptc.datasets()[0].data,
+            UTF8
+        );
+        assert_eq!(
+            iptc.get_all(Iptc::BY_LINE),
+            vec!["Jane", "John"]
+        );
+        assert_eq!(
+            iptc.get(Iptc::COPYRIGHT_NOTICE)
+                .as_deref(),
+            Some("© 2024 Jane")
+        );
+    }
+
+    #[test]
+    fn round_trip_resource_block() {
+        let mut iptc = sample();
+        iptc.resources = Some(vec![
+            Resource {
+                id: 0x040C,
+                name: b"odd".to_vec(),
+                data: vec![1, 2, 3],
+            },
+        ]);
+        let bytes = iptc.to_bytes();
+        assert!(bytes.starts_with(PHOTOSHOP_HEADER));
+        let parsed = Iptc::parse(&bytes).unwrap();
+        assert_eq!(
+            parsed.get_all(Iptc::BY_LINE),
+            iptc.get_all(Iptc::BY_LINE)
+        );
+        assert_eq!(
+            parsed.resources,
+            iptc.resources
+        );
+        assert_eq!(
+            parsed.to_bytes(),
+            bytes
+        );
+    }
+
+    #[test]
+    fn round_trip_iim() {
+        let mut iptc = sample();
+        iptc.set(
+            Iptc::CAPTION,
+            &["x"
+                .repeat(0x9000)
+                .as_str()],
+        )
+        .unwrap();
+        let iim = iptc.to_iim();
+        let parsed = Iptc::parse(&iim).unwrap();
+        assert_eq!(
+            parsed.resources,
+            None
+        );
+        assert_eq!(
+            parsed
+                .get(Iptc::CAPTION)
+                .map(|caption| caption.len()),
+            Some(0x9000)
+        );
+        assert_eq!(
+            parsed.to_iim(),
+            iim
+        );
+    }
+
+    #[test]
+    fn other_charset_is_rewritten_or_rejected() {
+        let latin1 = IptcDataSet {
+            record: 1,
+            dataset: 90,
+            data: b"\x1b.A".to_vec(),
+        };
+        let mut ascii = Iptc {
+            datasets: vec![latin1.clone()],
+            resources: None,
+        };
+        ascii
+            .set(
+                Iptc::CITY,
+                &["Paris"],
+            )
+            .unwrap();
+        ascii
+            .set(
+                Iptc::CAPTION,
+                &["Café"],
+            )
+            .unwrap();
+        assert_eq!(
+            ascii.datasets()[0].data,
+            UTF8
+        );
+
+        let mut accented = Iptc {
+            datasets: vec![
+                latin1,
+                IptcDataSet {
+                    record: 2,
+                    dataset: 90,
+                    data: b"Z\xfcrich".to_vec(),
+                },
+            ],
+            resources: None,
+        };
+        assert!(accented
+            .set(
+                Iptc::CAPTION,
+                &["Café"]
+            )
+            .is_err());
+        assert_eq!(
+            accented.datasets()[0].data,
+            b"\x1b.A"
+        );
+    }
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let bytes = sample().to_bytes();
+        let header = PHOTOSHOP_HEADER.len();
+        for len in header + RESOURCE_SIGNATURE.len()..bytes.len() - 1 {
+            assert!(
+                Iptc::parse(&bytes[..len]).is_err(),
+                "{} bytes",
+                len
+            );
+        }
+        assert!(Iptc::parse(&[TAG_MARKER, 2, 80, 0]).is_err());
+        assert!(Iptc::parse(&[TAG_MARKER, 2, 80, 0x80, 0x08]).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    enums::ForeignKeep,
+    iptc::IPTC_DATA,
+    voption::{Setter, VOption},
+    xmp::XMP_DATA,
+    Iptc, Result, VipsImage, Xmp,
+};
+use std::path::Path;
+
+/// XMP properties kept by `KeepPolicy::xmp_rights_only()`
+const XMP_RIGHTS: &[&str] = &[
+    "dc:rights",
+    "dc:creator",
+    "xmpRights:",
+    "plus:",
+    "photoshop:Credit",
+    "photoshop:Source",
+];
+
+/// Which metadata a saver retains.
+///
+/// The coarse [`ForeignKeep`] flags are passed to the saver as `keep`, and the finer rules are applied
+/// to a copy of the image by removing metadata fields, so the same policy gives the same result with
+/// `jpegsave`, `webpsave`, `heifsave`, `pngsave`, `tiffsave` and `jxlsave`.
+///
+/// ```no_run
+/// use rs_vips::{voption::{Setter, VOption}, KeepPolicy, VipsImage};
+///
+/// let image = VipsImage::new_from_file("input.jpg").unwrap();
+/// let policy = KeepPolicy::exif_without_gps();
+/// image
+///     .write_to_file_with_policy("output.jpg", &policy, VOption::new().set("Q", 85))
+///     .unwrap();
+///
+/// // With a saver, apply the policy to the image and pass its flags as `keep`
+/// image
+///     .with_keep_policy(&policy)
+///     .unwrap()
+///     .webpsave_with_opts("output.webp", VOption::new().set("keep", policy.flags()))
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeepPolicy {
+    flags: i32,
+    strip_gps: bool,
+    xmp_properties: Option<Vec<String>>,
+    iptc_datasets: Option<Vec<(u8, u8)>>,
+    removed_fields: Vec<String>,
+}
+
+impl KeepPolicy {
+    /// Keeps no metadata.
+    pub fn none() -> KeepPolicy {
+        KeepPolicy::default()
+    }
+
+    /// Keeps all metadata.
+    pub fn all() -> KeepPolicy {
+        KeepPolicy::none().keep(ForeignKeep::All)
+    }
+
+    /// Keeps EXIF without the GPS tags.
+    pub fn exif_without_gps() -> KeepPolicy {
+        KeepPolicy::none()
+            .keep(ForeignKeep::Exif)
+            .without_gps()
+    }
+
+    /// Keeps the ICC profile only.
+    pub fn icc_only() -> KeepPolicy {
+        KeepPolicy::none().keep(ForeignKeep::Icc)
+    }
+
+    /// Keeps only the rights and creator properties of XMP.
+    pub fn xmp_rights_only() -> KeepPolicy {
+        KeepPolicy::none()
+            .keep(ForeignKeep::Xmp)
+            .xmp_properties(XMP_RIGHTS)
+    }
+
+    /// Keeps a kind of metadata in addition to the ones already kept.
+    pub fn keep(mut self, keep: ForeignKeep) -> KeepPolicy {
+        self.flags |= keep as i32;
+        self
+    }
+
+    /// Removes the EXIF GPS tags, and the `exif:GPS` properties of a kept XMP packet.
+    pub fn without_gps(mut self) -> KeepPolicy {
+        self.strip_gps = true;
+        self
+    }
+
+    /// Keeps only the listed XMP properties. An entry ending with `:`, such as `xmpRights:`, matches a whole namespace.
+    /// A packet which cannot be parsed is removed.
+    pub fn xmp_properties(mut self, names: &[&str]) -> KeepPolicy {
+        self.xmp_properties = Some(
+            names
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        );
+        self
+    }
+
+    /// Keeps only the listed IPTC datasets, e.g. `Iptc::COPYRIGHT_NOTICE`. Data which cannot be parsed is removed.
+    pub fn iptc_datasets(mut self, datasets: &[(u8, u8)]) -> KeepPolicy {
+        self.iptc_datasets = Some(datasets.to_vec());
+        self
+    }
+
+    /// Removes a metadata field, e.g. `exif-ifd0-Software`.
+    pub fn remove_field(mut self, name: &str) -> KeepPolicy {
+        self.removed_fields
+            .push(name.to_string());
+        self
+    }
+
+    /// The [`ForeignKeep`] flags to pass to savers as `keep`
+    pub fn flags(&self) -> i32 {
+        self.flags
+    }
+
+    fn keeps(&self, keep: ForeignKeep) -> bool {
+        self.flags & keep as i32 != 0
+    }
+
+    fn keeps_xmp_property(&self, property: &str) -> bool {
+        if self.strip_gps && property.starts_with("exif:GPS") {
+            return false;
+        }
+        match &self.xmp_properties {
+            Some(names) => names
+                .iter()
+                .any(|name| {
+                    property == name || name.ends_with(':') && property.starts_with(name.as_str())
+                }),
+            None => true,
+        }
+    }
+
+    fn apply(&self, image: &mut VipsImage) -> Result<()> {
+        if self.strip_gps && self.keeps(ForeignKeep::Exif) {
+            image.remove_exif_gps()?;
+        }
+
+        if self.keeps(ForeignKeep::Xmp)
+            && (self
+                .xmp_properties
+                .is_some()
+                || self.strip_gps)
+        {
+            if let Ok(data) = image.get_blob(XMP_DATA) {
+                match Xmp::parse(&data) {
+                    Ok(mut xmp) => {
+                        let mut removed = false;
+                        xmp.retain(
+                            |property, _| {
+                                let keep = self.keeps_xmp_property(property);
+                                removed |= !keep;
+                                keep
+                            },
+                        );
+                        if removed {
+                            image.set_xmp(&xmp)?;
+                        }
+                    }
+                    // Properties of a packet which cannot be parsed cannot be filtered either
+                    Err(_) => {
+                        image.remove(XMP_DATA)?;
+                    }
+                }
+            }
+        }
+
+        if let (Some(datasets), true) = (
+            &self.iptc_datasets,
+            self.keeps(ForeignKeep::Iptc),
+        ) {
+            if let Ok(data) = image.get_blob(IPTC_DATA) {
+                match Iptc::parse(&data) {
+                    Ok(iptc) => {
+                        let mut kept = iptc.clone();
+                        for set in iptc.datasets() {
+                            let dataset = (
+                                set.record,
+                                set.dataset,
+                            );
+                            // The character set and record version describe the datasets that remain
+                            if dataset != Iptc::CODED_CHARACTER_SET
+                                && dataset != Iptc::RECORD_VERSION
+                                && !datasets.contains(&dataset)
+                            {
+                                kept.remove(dataset);
+                            }
+                        }
+                        image.set_iptc(&kept)?;
+                    }
+                    Err(_) => {
+                        image.remove(IPTC_DATA)?;
+                    }
+                }
+            }
+        }
+
+        for name in &self.removed_fields {
+            image.remove(name)?;
+        }
+        Ok(())
+    }
+}
+
+impl VipsImage {
+    /// Returns a copy of the image with the metadata removed which the policy drops below the granularity of
+    /// the `keep` flags of savers. Pass [`KeepPolicy::flags()`] as `keep` when saving the copy.
+    pub fn with_keep_policy(&self, policy: &KeepPolicy) -> Result<VipsImage> {
+        let mut out = self.copy()?;
+        policy.apply(&mut out)?;
+        Ok(out)
+    }
+
+    /// Writes this image to a file on disc, retaining metadata according to the policy.
+    /// The policy replaces any `keep` in `option`.
+    pub fn write_to_file_with_policy<P: AsRef<Path>>(
+        &self,
+        filename: P,
+        policy: &KeepPolicy,
+        option: VOption,
+    ) -> Result<()> {
+        self.with_keep_policy(policy)?
+            .write_to_file_with_opts(
+                filename,
+                option
+                    .without("keep")
+                    .set(
+                        "keep",
+                        policy.flags(),
+                    ),
+            )
+    }
+
+    /// Writes this image to memory, retaining metadata according to the policy.
+    /// The policy replaces any `keep` in `option`.
+    pub fn write_to_buffer_with_policy(
+        &self,
+        suffix: &str,
+        policy: &KeepPolicy,
+        option: VOption,
+    ) -> Result<Vec<u8>> {
+        self.with_keep_policy(policy)?
+            .write_to_buffer_with_opts(
+                suffix,
+                option
+                    .without("keep")
+                    .set(
+                        "keep",
+                        policy.flags(),
+                    ),
+            )
+    }
+}
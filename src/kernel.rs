@@ -0,0 +1,225 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    enums::Precision,
+    voption::{Setter, VOption},
+    Result, VipsImage, VipsMatrix,
+};
+
+/// Mask value of a structuring element for pixels which must be set
+const SET: f64 = 255.0;
+/// Mask value of a structuring element for pixels which are ignored
+const DONT_CARE: f64 = 128.0;
+
+/// Ready-to-use masks for `conv`, `convsep` and `compass`, with `scale` and `offset` set.
+///
+/// ```no_run
+/// use rs_vips::{ConvolutionKernel, VipsImage};
+///
+/// let image = VipsImage::new_from_file("photo.jpg").unwrap();
+/// let edges = image
+///     .compass(&ConvolutionKernel::sobel_x().unwrap())
+///     .unwrap();
+/// let sharpened = image
+///     .conv(&ConvolutionKernel::unsharp(1.5, 0.8).unwrap())
+///     .unwrap();
+/// ```
+pub struct ConvolutionKernel;
+
+impl ConvolutionKernel {
+    /// A `size` x `size` mean filter
+    pub fn box_blur(size: usize) -> Result<VipsImage> {
+        VipsMatrix::from_vec(
+            size,
+            size,
+            vec![1.0; size * size],
+        )?
+        .with_scale((size * size) as f64)
+        .to_image()
+    }
+
+    /// A gaussian blur, made by `gaussmat`
+    pub fn gaussian(sigma: f64, min_ampl: f64) -> Result<VipsImage> {
+        VipsImage::gaussmat(
+            sigma,
+            min_ampl,
+        )
+    }
+
+    /// A one-row gaussian for `convsep`, made by `gaussmat`
+    pub fn gaussian_separable(sigma: f64, min_ampl: f64) -> Result<VipsImage> {
+        VipsImage::gaussmat_with_opts(
+            sigma,
+            min_ampl,
+            VOption::new().set(
+                "separable",
+                true,
+            ),
+        )
+    }
+
+    /// A laplacian of gaussian, made by `logmat`
+    pub fn laplacian_of_gaussian(sigma: f64, min_ampl: f64) -> Result<VipsImage> {
+        VipsImage::logmat(
+            sigma,
+            min_ampl,
+        )
+    }
+
+    /// Horizontal gradient. Use with `compass` or on a signed format, as the result can be negative.
+    pub fn sobel_x() -> Result<VipsImage> {
+        VipsMatrix::from_vec2(&[
+            vec![-1.0, 0.0, 1.0],
+            vec![-2.0, 0.0, 2.0],
+            vec![-1.0, 0.0, 1.0],
+        ])?
+        .to_image()
+    }
+
+    /// Vertical gradient. Use with `compass` or on a signed format, as the result can be negative.
+    pub fn sobel_y() -> Result<VipsImage> {
+        VipsMatrix::from_vec2(&[
+            vec![-1.0, -2.0, -1.0],
+            vec![0.0, 0.0, 0.0],
+            vec![1.0, 2.0, 1.0],
+        ])?
+        .to_image()
+    }
+
+    pub fn prewitt_x() -> Result<VipsImage> {
+        VipsMatrix::from_vec2(&[
+            vec![-1.0, 0.0, 1.0],
+            vec![-1.0, 0.0, 1.0],
+            vec![-1.0, 0.0, 1.0],
+        ])?
+        .to_image()
+    }
+
+    pub fn prewitt_y() -> Result<VipsImage> {
+        VipsMatrix::from_vec2(&[
+            vec![-1.0, -1.0, -1.0],
+            vec![0.0, 0.0, 0.0],
+            vec![1.0, 1.0, 1.0],
+        ])?
+        .to_image()
+    }
+
+    /// A 4-neighbour laplacian, offset by 128 so that edges of 8-bit images stay in range
+    pub fn laplacian() -> Result<VipsImage> {
+        VipsMatrix::from_vec2(&[
+            vec![0.0, -1.0, 0.0],
+            vec![-1.0, 4.0, -1.0],
+            vec![0.0, -1.0, 0.0],
+        ])?
+        .with_offset(128.0)
+        .to_image()
+    }
+
+    /// Sharpens by subtracting `amount` times a gaussian blur of `sigma` from the image.
+    pub fn unsharp(sigma: f64, amount: f64) -> Result<VipsImage> {
+        let gaussian = VipsMatrix::from_image(
+            &VipsImage::gaussmat_with_opts(
+                sigma,
+                0.2,
+                VOption::new().set(
+                    "precision",
+                    Precision::Float as i32,
+                ),
+            )?,
+        )?;
+        let scale = gaussian.scale();
+        let (rows, cols) = (
+            gaussian.rows(),
+            gaussian.cols(),
+        );
+        let mut kernel = &gaussian * (-amount / scale);
+        let centre = kernel
+            .get(
+                rows / 2,
+                cols / 2,
+            )
+            .unwrap_or_default();
+        kernel.set(
+            rows / 2,
+            cols / 2,
+            centre + 1.0 + amount,
+        );
+        kernel
+            .with_scale(1.0)
+            .to_image()
+    }
+}
+
+/// Ready-to-use structuring elements for `morph`. Pixels of the shape are 255 and the rest are 128, don't care.
+///
+/// ```no_run
+/// use rs_vips::{enums::OperationMorphology, StructuringElement, VipsImage};
+///
+/// let mask = VipsImage::new_from_file("mask.png").unwrap();
+/// let dilated = mask
+///     .morph(&StructuringElement::disk(3).unwrap(), OperationMorphology::Dilate)
+///     .unwrap();
+/// ```
+pub struct StructuringElement;
+
+impl StructuringElement {
+    /// Makes a `size` x `size` element from a predicate on positions relative to the centre.
+    fn shape(size: usize, inside: impl Fn(f64, f64) -> bool) -> Result<VipsImage> {
+        let centre = (size / 2) as f64;
+        let mut matrix = VipsMatrix::new(size, size);
+        for row in 0..size {
+            for col in 0..size {
+                let value = if inside(
+                    col as f64 - centre,
+                    row as f64 - centre,
+                ) {
+                    SET
+                } else {
+                    DONT_CARE
+                };
+                matrix.set(
+                    row, col, value,
+                );
+            }
+        }
+        matrix.to_image()
+    }
+
+    /// A `size` x `size` square
+    pub fn square(size: usize) -> Result<VipsImage> {
+        StructuringElement::shape(
+            size,
+            |_, _| true,
+        )
+    }
+
+    /// A disk of `radius` pixels around the centre
+    pub fn disk(radius: usize) -> Result<VipsImage> {
+        let limit = (radius as f64 + 0.5).powi(2);
+        StructuringElement::shape(
+            2 * radius + 1,
+            |x, y| x * x + y * y <= limit,
+        )
+    }
+
+    /// A plus sign with arms of `radius` pixels
+    pub fn cross(radius: usize) -> Result<VipsImage> {
+        StructuringElement::shape(
+            2 * radius + 1,
+            |x, y| x == 0.0 || y == 0.0,
+        )
+    }
+
+    /// A line of `2 * radius + 1` pixels through the centre, at `angle` degrees clockwise from horizontal
+    pub fn line(radius: usize, angle: f64) -> Result<VipsImage> {
+        let (sin, cos) = angle
+            .to_radians()
+            .sin_cos();
+        StructuringElement::shape(
+            2 * radius + 1,
+            |x, y| {
+                // Distance from the line, within half a pixel
+                (x * sin - y * cos).abs() <= 0.5
+            },
+        )
+    }
+}
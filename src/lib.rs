@@ -23,6 +23,8 @@
 //!
 //! Shutdown is optional. You can shut down by [`Vips::shutdown()`]. Once Vips is shut down, all operations including [`Vips::init()`] are no longer available.
 //!
+//! Alternatively, [`Vips::init_context()`] returns a [`VipsContext`] guard. libvips stays initialized while any guard is alive and is shut down when the last one is dropped, unless [`Vips::init()`] was called, which keeps libvips initialized until [`Vips::shutdown()`].
+//!
 //! Many vips operations have optional arguments. Basically there'll be a regular call with only the required parameters and an additional with the suffix `with_opts` which takes [`voption::VOption`] containing optional arguments.
 //!
 //! ```no_run
@@ -96,45 +98,117 @@
 extern crate num_derive;
 extern crate num_traits;
 
+mod affine;
+mod animation;
 pub mod bindings;
+mod color;
+mod composite;
 mod connection;
+mod context;
 /// Vips Enumerations
 pub mod enums;
 pub mod error;
+mod exif;
+mod geometry;
+mod histogram;
+mod icc;
 mod image;
 mod interpolate;
+mod iptc;
+mod keep;
+mod kernel;
+mod logging;
+mod matrix;
+mod metadata;
 pub mod operator;
 mod ops;
+mod overlay;
+mod pages;
 mod region;
 mod source;
+mod statistics;
+mod stats;
 mod target;
+mod text;
+#[cfg(feature = "tracing")]
+mod trace;
 mod utils;
 /// VOption, a list of name-value pairs
 pub mod voption;
+mod xmp;
 
+pub use affine::{Affine2D, OutputArea};
+pub use animation::Animation;
+pub use color::Color;
+pub use composite::Layer;
+pub use context::VipsContext;
 use error::Error;
+pub use exif::{Exif, GpsCoordinates};
+pub use geometry::{Dimensions, Point, Rect};
+pub use histogram::Histogram;
+pub use icc::*;
 pub use image::*;
 pub use interpolate::*;
+pub use iptc::*;
+pub use keep::KeepPolicy;
+pub use kernel::{ConvolutionKernel, StructuringElement};
+pub use logging::{LogLevel, LogMessage};
+pub use matrix::VipsMatrix;
+pub use metadata::*;
+pub use overlay::Placement;
+pub use pages::{PageFit, PageWriter, Pages};
 pub use region::*;
 pub use source::*;
+pub use statistics::{BandStats, ImageStats};
+pub use stats::*;
 use std::ffi::CStr;
 pub use target::*;
+pub use text::{escape_markup, FontWeight, TextBuilder, TextSpan};
+pub use xmp::*;
 
 pub type Result<T> = std::result::Result<T, error::Error>;
 
+/// Operations blocked by [`Vips::block_web_unsafe()`] on top of the ones libvips flags as untrusted.
+/// Names of operations which are not built into libvips are ignored.
+pub const WEB_UNSAFE_OPERATIONS: &[&str] = &[
+    "system",
+    "matrixload",
+    "matrixload_source",
+    "csvload",
+    "csvload_source",
+    "rawload",
+    "vipsload",
+    "vipsload_source",
+    "fitsload",
+    "fitsload_source",
+    "niftiload",
+    "niftiload_source",
+    "analyze2vips",
+    "openslideload",
+    "openslideload_source",
+    "magickload",
+    "magickload_buffer",
+    "magickload_source",
+];
+
 /// Basic utility struct. Use it to initialize/shutdown the system
 pub struct Vips;
 
 impl Vips {
-    /// Starts up libvips
+    /// Starts up libvips. It is safe to call this many times from any thread, libvips is initialized only once.
+    /// libvips then stays initialized until [`Vips::shutdown()`], even when every [`VipsContext`] is dropped.
     pub fn init(name: &str) -> Result<()> {
-        let c_name = utils::new_c_string(name)?;
-        let res = unsafe { bindings::vips_init(c_name.as_ptr()) };
-        if res == 0 {
-            Ok(())
-        } else {
-            Err(Error::InitializationError("Failed to init libvips".to_string()))
-        }
+        context::init(name)
+    }
+
+    /// Starts up libvips and returns a guard which shuts libvips down when the last guard is dropped.
+    pub fn init_context(name: &str) -> Result<VipsContext> {
+        VipsContext::new(name)
+    }
+
+    /// Returns true if libvips is initialized and not shut down yet.
+    pub fn is_initialized() -> bool {
+        context::is_initialized()
     }
 
     /// Turn on or off vips leak checking.
@@ -314,6 +388,18 @@ impl Vips {
         unsafe { bindings::vips_tracked_get_files() }
     }
 
+    /// Returns a snapshot of the cache size and limits and the tracked memory, allocations and files.
+    pub fn stats() -> VipsStats {
+        VipsStats::snapshot()
+    }
+
+    /// Returns every operation alive in libvips: the ones kept by the operation cache and the ones still running or referenced.
+    /// Unlike `cache_print`, this does not print to stdout. Memory held by each operation is not reported,
+    /// as the outputs of an operation can be freed by another thread while they are read.
+    pub fn live_operations() -> Vec<LiveOperation> {
+        stats::live_operations()
+    }
+
     /// If a source does not support mmap or seek and the source is used with a loader that can only work from memory, then the data will be automatically read into memory to EOF before the loader starts.
     pub fn pipe_read_limit_set(limit: i64) {
         unsafe {
@@ -321,11 +407,80 @@ impl Vips {
         }
     }
 
+    /// Set the block state on all untrusted operations.
+    pub fn block_untrusted(state: bool) {
+        unsafe {
+            bindings::vips_block_untrusted_set(if state { 1 } else { 0 });
+        }
+    }
+
+    /// Set the block state on all operations in the libvips class hierarchy at `name` and below.
+    /// `name` can be either a type name such as `VipsForeignLoad` or a nickname such as `jpegload`.
+    pub fn operation_block(name: &str, state: bool) -> Result<()> {
+        let c_name = utils::new_c_string(name)?;
+        unsafe {
+            bindings::vips_operation_block_set(
+                c_name.as_ptr(),
+                if state { 1 } else { 0 },
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns true if the operation with the given type name or nickname is blocked.
+    pub fn is_operation_blocked(name: &str) -> Result<bool> {
+        let c_name = utils::new_c_string(name)?;
+        utils::is_operation_blocked(c_name.as_ptr()).ok_or(
+            Error::OperationError(format!(
+                "Unknown operation: {}",
+                name
+            )),
+        )
+    }
+
+    /// Blocks untrusted operations plus every operation in [`WEB_UNSAFE_OPERATIONS`].
+    /// Use this before processing user uploads. Pass `false` to lift the blocks again.
+    pub fn block_web_unsafe(state: bool) -> Result<()> {
+        Self::block_untrusted(state);
+        for name in WEB_UNSAFE_OPERATIONS {
+            Self::operation_block(
+                name, state,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Install a GLib log handler which routes libvips/GLib log output into `log` or `tracing` instead of stderr.
+    /// Enable the `log` or `tracing` feature to forward messages. Without them, messages still go to stderr.
+    /// With both features enabled, messages go to `tracing` only.
+    ///
+    /// The handler which was installed before is still called, except GLib's stderr handler when messages are forwarded.
+    pub fn install_log_handler() {
+        logging::install_log_handler();
+    }
+
+    /// Restores the GLib log handler which `install_log_handler()` replaced.
+    pub fn uninstall_log_handler() {
+        logging::uninstall_log_handler();
+    }
+
+    /// Runs `f` and returns its result with the warnings (and errors) libvips logged meanwhile.
+    /// Use it to flag partially corrupt images, e.g. `Vips::collect_warnings(|| image.write_to_buffer(".png"))`.
+    ///
+    /// Messages are collected from every thread, as libvips logs from its worker threads, so warnings of
+    /// operations running concurrently on other threads can be included.
+    pub fn collect_warnings<T, F: FnOnce() -> T>(
+        f: F,
+    ) -> (
+        T,
+        Vec<LogMessage>,
+    ) {
+        logging::collect_warnings(f)
+    }
+
     /// Call this to drop caches, close plugins, terminate background threads, and finalize any internal library testing.
     /// vips_shutdown() is optional.
     pub fn shutdown() {
-        unsafe {
-            bindings::vips_shutdown();
-        }
+        context::shutdown();
     }
 }
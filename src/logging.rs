@@ -0,0 +1,238 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    bindings::{
+        self, gchar, gpointer, GLogFunc, GLogLevelFlags, GLogLevelFlags_G_LOG_LEVEL_CRITICAL,
+        GLogLevelFlags_G_LOG_LEVEL_DEBUG, GLogLevelFlags_G_LOG_LEVEL_ERROR,
+        GLogLevelFlags_G_LOG_LEVEL_INFO, GLogLevelFlags_G_LOG_LEVEL_MESSAGE,
+        GLogLevelFlags_G_LOG_LEVEL_WARNING,
+    },
+    utils::new_string_from_ptr,
+};
+use std::{
+    ptr::null_mut,
+    sync::{Arc, Mutex},
+};
+
+/// Severity of a GLib log message
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Critical,
+    Warning,
+    Message,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    fn from_flags(flags: GLogLevelFlags) -> LogLevel {
+        if flags & GLogLevelFlags_G_LOG_LEVEL_ERROR != 0 {
+            LogLevel::Error
+        } else if flags & GLogLevelFlags_G_LOG_LEVEL_CRITICAL != 0 {
+            LogLevel::Critical
+        } else if flags & GLogLevelFlags_G_LOG_LEVEL_WARNING != 0 {
+            LogLevel::Warning
+        } else if flags & GLogLevelFlags_G_LOG_LEVEL_MESSAGE != 0 {
+            LogLevel::Message
+        } else if flags & GLogLevelFlags_G_LOG_LEVEL_INFO != 0 {
+            LogLevel::Info
+        } else if flags & GLogLevelFlags_G_LOG_LEVEL_DEBUG != 0 {
+            LogLevel::Debug
+        } else {
+            LogLevel::Message
+        }
+    }
+}
+
+/// A message logged by libvips or GLib, such as `VipsJpeg: premature end of JPEG file`
+#[derive(Clone, Debug)]
+pub struct LogMessage {
+    /// Log domain, e.g. `VIPS` or `GLib-GObject`
+    pub domain: String,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+type Collector = Arc<Mutex<Vec<LogMessage>>>;
+
+/// The installed handler and the GLib default handler it replaced
+struct Handler {
+    installed: bool,
+    previous: GLogFunc,
+}
+
+static HANDLER: Mutex<Handler> = Mutex::new(Handler {
+    installed: false,
+    previous: None,
+});
+static COLLECTORS: Mutex<Vec<Collector>> = Mutex::new(Vec::new());
+
+/// Installs the GLib default log handler which forwards messages to `log` or `tracing` when either feature is enabled,
+/// and to the collectors of `collect_warnings()`. The handler it replaces is kept and called for every message, except
+/// the stock stderr handler when messages are forwarded.
+pub(crate) fn install_log_handler() {
+    let mut handler = lock(&HANDLER);
+    if !handler.installed {
+        handler.previous = unsafe {
+            bindings::g_log_set_default_handler(
+                Some(log_handler),
+                null_mut(),
+            )
+        };
+        handler.installed = true;
+    }
+}
+
+/// Restores the GLib default log handler which `install_log_handler()` replaced.
+pub(crate) fn uninstall_log_handler() {
+    let mut handler = lock(&HANDLER);
+    if handler.installed {
+        unsafe {
+            bindings::g_log_set_default_handler(
+                handler.previous,
+                null_mut(),
+            );
+        }
+        handler.installed = false;
+        handler.previous = None;
+    }
+}
+
+/// Removes a collector when `collect_warnings()` returns or unwinds.
+struct CollectorGuard(Collector);
+
+impl Drop for CollectorGuard {
+    fn drop(&mut self) {
+        lock(&COLLECTORS).retain(|active| {
+            !Arc::ptr_eq(
+                active,
+                &self.0,
+            )
+        });
+    }
+}
+
+/// Runs `f` and returns its result together with the warnings logged while it was running.
+/// Collectors are process-wide, as libvips logs from its worker threads, so warnings of other threads are included.
+pub(crate) fn collect_warnings<T, F: FnOnce() -> T>(
+    f: F,
+) -> (
+    T,
+    Vec<LogMessage>,
+) {
+    install_log_handler();
+
+    let collector: Collector = Arc::default();
+    lock(&COLLECTORS).push(collector.clone());
+    let guard = CollectorGuard(collector.clone());
+
+    let output = f();
+
+    drop(guard);
+    let messages = std::mem::take(&mut *lock(&collector));
+    (
+        output,
+        messages,
+    )
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+unsafe extern "C" fn log_handler(
+    log_domain: *const gchar,
+    log_level: GLogLevelFlags,
+    message: *const gchar,
+    user_data: gpointer,
+) {
+    let level = LogLevel::from_flags(log_level);
+
+    let collectors = lock(&COLLECTORS);
+    if level <= LogLevel::Warning && !collectors.is_empty() {
+        let log_message = LogMessage {
+            domain: new_string_from_ptr(log_domain),
+            level,
+            message: new_string_from_ptr(message),
+        };
+        for collector in collectors.iter() {
+            lock(collector).push(log_message.clone());
+        }
+    }
+    drop(collectors);
+
+    let forwarded = cfg!(any(
+        feature = "log",
+        feature = "tracing"
+    ));
+    if forwarded {
+        forward(
+            &new_string_from_ptr(log_domain),
+            level,
+            &new_string_from_ptr(message),
+        );
+    }
+
+    // Chain to the replaced handler. GLib does not return its user data, so it gets ours, which is null.
+    let previous = lock(&HANDLER).previous;
+    match previous {
+        Some(previous) if previous as *const () != bindings::g_log_default_handler as *const () => {
+            previous(
+                log_domain,
+                log_level,
+                message,
+                user_data,
+            )
+        }
+        _ if !forwarded => bindings::g_log_default_handler(
+            log_domain,
+            log_level,
+            message,
+            user_data,
+        ),
+        _ => {}
+    }
+}
+
+/// Forwards a message to `tracing`, or to `log` when only that feature is enabled, so that it is emitted once.
+#[allow(unused_variables)]
+fn forward(domain: &str, level: LogLevel, message: &str) {
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    {
+        let log_level = match level {
+            LogLevel::Error | LogLevel::Critical => log::Level::Error,
+            LogLevel::Warning => log::Level::Warn,
+            LogLevel::Message | LogLevel::Info => log::Level::Info,
+            LogLevel::Debug => log::Level::Debug,
+        };
+        log::log!(target: domain, log_level, "{}", message);
+    }
+
+    #[cfg(feature = "tracing")]
+    {
+        match level {
+            LogLevel::Error | LogLevel::Critical => tracing::error!(
+                domain,
+                "{}",
+                message
+            ),
+            LogLevel::Warning => tracing::warn!(
+                domain,
+                "{}",
+                message
+            ),
+            LogLevel::Message | LogLevel::Info => tracing::info!(
+                domain,
+                "{}",
+                message
+            ),
+            LogLevel::Debug => tracing::debug!(
+                domain,
+                "{}",
+                message
+            ),
+        }
+    }
+}
@@ -0,0 +1,321 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{error::Error, utils, Result, VipsImage};
+use std::ops::Mul;
+
+/// A matrix of doubles, such as a convolution kernel or a `recomb` matrix, with the `scale` and `offset`
+/// which `conv` and friends apply to the result.
+///
+/// ```no_run
+/// use rs_vips::{VipsImage, VipsMatrix};
+///
+/// let image = VipsImage::new_from_file("photo.jpg").unwrap();
+/// let kernel = VipsMatrix::from_vec2(&[
+///     vec![1.0, 2.0, 1.0],
+///     vec![2.0, 4.0, 2.0],
+///     vec![1.0, 2.0, 1.0],
+/// ])
+/// .unwrap()
+/// .with_scale(16.0);
+/// let blurred = image
+///     .conv(&kernel.to_image().unwrap())
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct VipsMatrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+    scale: f64,
+    offset: f64,
+}
+
+impl VipsMatrix {
+    /// A matrix of zeros
+    pub fn new(rows: usize, cols: usize) -> VipsMatrix {
+        VipsMatrix {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+            scale: 1.0,
+            offset: 0.0,
+        }
+    }
+
+    pub fn identity(size: usize) -> VipsMatrix {
+        let mut matrix = VipsMatrix::new(size, size);
+        for i in 0..size {
+            matrix.data[i * size + i] = 1.0;
+        }
+        matrix
+    }
+
+    /// A matrix from values in row-major order
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<f64>) -> Result<VipsMatrix> {
+        if data.len() != rows * cols {
+            return Err(
+                Error::OperationError(format!(
+                    "Expected {} values for a {}x{} matrix, got {}",
+                    rows * cols,
+                    rows,
+                    cols,
+                    data.len()
+                )),
+            );
+        }
+        Ok(
+            VipsMatrix {
+                rows,
+                cols,
+                data,
+                scale: 1.0,
+                offset: 0.0,
+            },
+        )
+    }
+
+    /// A matrix from its rows, which must all have the same length
+    pub fn from_vec2(rows: &[Vec<f64>]) -> Result<VipsMatrix> {
+        let cols = rows
+            .first()
+            .map(Vec::len)
+            .unwrap_or_default();
+        if rows
+            .iter()
+            .any(|row| row.len() != cols)
+        {
+            return Err(Error::OperationError("Matrix rows must have the same length".to_string()));
+        }
+        VipsMatrix::from_vec(
+            rows.len(),
+            cols,
+            rows.concat(),
+        )
+    }
+
+    /// Reads a one-band image as a matrix, with its `scale` and `offset`.
+    pub fn from_image(image: &VipsImage) -> Result<VipsMatrix> {
+        if image.get_bands() != 1 {
+            return Err(Error::OperationError("Matrix images must have one band".to_string()));
+        }
+        let data = utils::to_f64_vec(image)?;
+        Ok(
+            VipsMatrix::from_vec(
+                image.get_height() as usize,
+                image.get_width() as usize,
+                data,
+            )?
+            .with_scale(image.get_scale())
+            .with_offset(image.get_offset()),
+        )
+    }
+
+    /// Makes a matrix image with `scale` and `offset` set.
+    pub fn to_image(&self) -> Result<VipsImage> {
+        let mut image = VipsImage::new_matrix_from_array(
+            self.cols as i32,
+            self.rows as i32,
+            &self.data,
+        )?;
+        image.set_double(
+            "scale",
+            self.scale,
+        )?;
+        image.set_double(
+            "offset",
+            self.offset,
+        )?;
+        Ok(image)
+    }
+
+    /// Loads a matrix file with `matrixload`.
+    pub fn load(filename: &str) -> Result<VipsMatrix> {
+        VipsMatrix::from_image(&VipsImage::matrixload(filename)?)
+    }
+
+    /// Saves the matrix with `matrixsave`.
+    pub fn save(&self, filename: &str) -> Result<()> {
+        self.to_image()?
+            .matrixsave(filename)
+    }
+
+    /// Prints the matrix to stdout with `matrixprint`.
+    pub fn print(&self) -> Result<()> {
+        self.to_image()?
+            .matrixprint()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The value at a row and column, or `None` if out of range
+    pub fn get(&self, row: usize, col: usize) -> Option<f64> {
+        if row >= self.rows || col >= self.cols {
+            return None;
+        }
+        Some(self.data[row * self.cols + col])
+    }
+
+    /// Sets the value at a row and column, returning `false` if out of range.
+    pub fn set(&mut self, row: usize, col: usize, value: f64) -> bool {
+        if row >= self.rows || col >= self.cols {
+            return false;
+        }
+        self.data[row * self.cols + col] = value;
+        true
+    }
+
+    /// Values in row-major order
+    pub fn data(&self) -> &[f64] {
+        &self.data
+    }
+
+    pub fn to_vec2(&self) -> Vec<Vec<f64>> {
+        self.data
+            .chunks(
+                self.cols
+                    .max(1),
+            )
+            .map(<[f64]>::to_vec)
+            .collect()
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Sets the divisor applied to the result of a convolution.
+    pub fn with_scale(mut self, scale: f64) -> VipsMatrix {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the value added to the result of a convolution.
+    pub fn with_offset(mut self, offset: f64) -> VipsMatrix {
+        self.offset = offset;
+        self
+    }
+
+    /// The sum of the values, the usual `scale` of a blur kernel
+    pub fn sum(&self) -> f64 {
+        self.data
+            .iter()
+            .sum()
+    }
+
+    pub fn transpose(&self) -> VipsMatrix {
+        let mut out = VipsMatrix::new(
+            self.cols,
+            self.rows,
+        );
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                out.data[col * self.rows + row] = self.data[row * self.cols + col];
+            }
+        }
+        out.with_scale(self.scale)
+            .with_offset(self.offset)
+    }
+
+    fn zip_with(&self, other: &VipsMatrix, f: impl Fn(f64, f64) -> f64) -> Result<VipsMatrix> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(
+                Error::OperationError(format!(
+                    "Matrix sizes differ: {}x{} and {}x{}",
+                    self.rows, self.cols, other.rows, other.cols
+                )),
+            );
+        }
+        VipsMatrix::from_vec(
+            self.rows,
+            self.cols,
+            self.data
+                .iter()
+                .zip(&other.data)
+                .map(|(a, b)| f(*a, *b))
+                .collect(),
+        )
+    }
+
+    /// Adds the matrices element by element.
+    pub fn try_add(&self, other: &VipsMatrix) -> Result<VipsMatrix> {
+        self.zip_with(
+            other,
+            |a, b| a + b,
+        )
+    }
+
+    /// Subtracts the matrices element by element.
+    pub fn try_sub(&self, other: &VipsMatrix) -> Result<VipsMatrix> {
+        self.zip_with(
+            other,
+            |a, b| a - b,
+        )
+    }
+
+    /// The matrix product, with `matrixmultiply`
+    pub fn matmul(&self, right: &VipsMatrix) -> Result<VipsMatrix> {
+        VipsMatrix::from_image(
+            &self
+                .to_image()?
+                .matrixmultiply(&right.to_image()?)?,
+        )
+    }
+
+    /// The inverse of a square matrix, with `matrixinvert`
+    pub fn invert(&self) -> Result<VipsMatrix> {
+        VipsMatrix::from_image(
+            &self
+                .to_image()?
+                .matrixinvert()?,
+        )
+    }
+}
+
+/// Multiplies every value, keeping `scale` and `offset`.
+impl Mul<f64> for &VipsMatrix {
+    type Output = VipsMatrix;
+
+    fn mul(self, rhs: f64) -> VipsMatrix {
+        VipsMatrix {
+            data: self
+                .data
+                .iter()
+                .map(|value| value * rhs)
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Mul<f64> for VipsMatrix {
+    type Output = VipsMatrix;
+
+    fn mul(self, rhs: f64) -> VipsMatrix {
+        &self * rhs
+    }
+}
+
+impl TryFrom<&VipsImage> for VipsMatrix {
+    type Error = Error;
+
+    fn try_from(image: &VipsImage) -> Result<Self> {
+        VipsMatrix::from_image(image)
+    }
+}
+
+impl TryFrom<&VipsMatrix> for VipsImage {
+    type Error = Error;
+
+    fn try_from(matrix: &VipsMatrix) -> Result<Self> {
+        matrix.to_image()
+    }
+}
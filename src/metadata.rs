@@ -0,0 +1,540 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    bindings::{self, gchar, GValue},
+    enums::*,
+    error::Error,
+    utils::{
+        ensure_null_terminated, get_g_type, new_string_from_ptr, new_vipsimage, G_TYPE_BOOLEAN,
+        G_TYPE_DOUBLE, G_TYPE_INT, G_TYPE_STRING, G_TYPE_UINT64,
+    },
+    Result, VipsImage,
+};
+use num_traits::FromPrimitive;
+use std::{
+    ffi::{c_void, CStr},
+    mem::MaybeUninit,
+};
+
+/// A metadata item of an image with its typed value
+#[derive(Clone, Debug)]
+pub enum MetaValue {
+    Bool(bool),
+    Int(i32),
+    Uint64(u64),
+    Double(f64),
+    /// A plain string or a `VipsRefString`
+    String(String),
+    Blob(Vec<u8>),
+    IntArray(Vec<i32>),
+    DoubleArray(Vec<f64>),
+    Image(VipsImage),
+    /// An enumeration value, e.g. `interpretation`, with its nickname
+    Enum {
+        value: i32,
+        nick: String,
+    },
+    /// A value of any other type converted to a string by libvips
+    Other(String),
+}
+
+/// Iterator over the metadata items of an image. Created by [`VipsImage::fields()`].
+pub struct Fields {
+    image: VipsImage,
+    names: std::vec::IntoIter<String>,
+}
+
+impl Iterator for Fields {
+    type Item = (
+        String,
+        MetaValue,
+    );
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for name in self
+            .names
+            .by_ref()
+        {
+            // Skip items removed after the iterator was made
+            if let Ok(value) = self
+                .image
+                .get_value(&name)
+            {
+                return Some((
+                    name, value,
+                ));
+            }
+        }
+        None
+    }
+}
+
+unsafe fn meta_value(
+    image: &VipsImage,
+    name: *const gchar,
+    gvalue: *mut GValue,
+) -> Result<MetaValue> {
+    let gtype = (*gvalue).g_type;
+
+    let value = if gtype == get_g_type(G_TYPE_BOOLEAN)? {
+        MetaValue::Bool(bindings::g_value_get_boolean(gvalue) != 0)
+    } else if gtype == get_g_type(G_TYPE_INT)? {
+        MetaValue::Int(bindings::g_value_get_int(gvalue))
+    } else if gtype == get_g_type(G_TYPE_UINT64)? {
+        MetaValue::Uint64(bindings::g_value_get_uint64(gvalue))
+    } else if gtype == get_g_type(G_TYPE_DOUBLE)? {
+        MetaValue::Double(bindings::g_value_get_double(gvalue))
+    } else if gtype == get_g_type(G_TYPE_STRING)? {
+        MetaValue::String(new_string_from_ptr(bindings::g_value_get_string(gvalue)))
+    } else if gtype == bindings::vips_ref_string_get_type() {
+        let mut length = 0;
+        MetaValue::String(
+            new_string_from_ptr(
+                bindings::vips_value_get_ref_string(
+                    gvalue,
+                    &mut length,
+                ),
+            ),
+        )
+    } else if gtype == bindings::vips_blob_get_type() {
+        let mut length = 0;
+        let ptr = bindings::vips_value_get_blob(
+            gvalue,
+            &mut length,
+        );
+        if ptr.is_null() || length == 0 {
+            MetaValue::Blob(Vec::new())
+        } else {
+            MetaValue::Blob(
+                std::slice::from_raw_parts(
+                    ptr as *const u8,
+                    length,
+                )
+                .to_vec(),
+            )
+        }
+    } else if gtype == bindings::vips_array_int_get_type() {
+        let mut size = 0;
+        let array = bindings::vips_value_get_array_int(
+            gvalue,
+            &mut size,
+        );
+        MetaValue::IntArray(
+            crate::utils::new_int_array(
+                array,
+                size as u64,
+            ),
+        )
+    } else if gtype == bindings::vips_array_double_get_type() {
+        let mut size = 0;
+        let array = bindings::vips_value_get_array_double(
+            gvalue,
+            &mut size,
+        );
+        MetaValue::DoubleArray(
+            crate::utils::new_double_array(
+                array,
+                size as u64,
+            ),
+        )
+    } else if bindings::g_type_is_a(
+        gtype,
+        bindings::vips_image_get_type(),
+    ) != 0
+    {
+        // Take our own reference since the GValue drops its one on unset
+        let ctx = bindings::g_value_get_object(gvalue);
+        bindings::g_object_ref(ctx);
+        MetaValue::Image(
+            new_vipsimage(
+                ctx as *mut bindings::VipsImage,
+                None,
+                None,
+            ),
+        )
+    } else if bindings::g_type_fundamental(gtype) == get_g_type("GEnum")? {
+        let value = bindings::g_value_get_enum(gvalue);
+        MetaValue::Enum {
+            value,
+            nick: new_string_from_ptr(
+                bindings::vips_enum_nick(
+                    gtype, value,
+                ),
+            ),
+        }
+    } else {
+        let mut out: *mut gchar = std::ptr::null_mut();
+        if bindings::vips_image_get_as_string(
+            image
+                .image
+                .ctx,
+            name,
+            &mut out,
+        ) != 0
+        {
+            return Err(Error::IOError("Cannot get value as string".to_string()).extend());
+        }
+        let string = new_string_from_ptr(out);
+        bindings::g_free(out as *mut c_void);
+        MetaValue::Other(string)
+    };
+
+    Ok(value)
+}
+
+impl VipsImage {
+    /// Gets the names of all metadata items on the image.
+    pub fn get_fields(&self) -> Vec<String> {
+        unsafe {
+            let fields = bindings::vips_image_get_fields(
+                self.image
+                    .ctx,
+            );
+            if fields.is_null() {
+                return Vec::new();
+            }
+
+            let mut names = Vec::new();
+            let mut field = fields;
+            while !(*field).is_null() {
+                names.push(new_string_from_ptr(*field));
+                field = field.add(1);
+            }
+            bindings::g_strfreev(fields);
+            names
+        }
+    }
+
+    /// Returns an iterator over the names and typed values of all metadata items on the image.
+    pub fn fields(&self) -> Fields {
+        Fields {
+            image: self.clone(),
+            names: self
+                .get_fields()
+                .into_iter(),
+        }
+    }
+
+    /// Gets the typed value of the metadata item under the name.
+    pub fn get_value(&self, name: impl AsRef<[u8]>) -> Result<MetaValue> {
+        unsafe {
+            let name = ensure_null_terminated(name)?;
+            let mut gvalue = MaybeUninit::<GValue>::zeroed();
+            let gvalue_ptr = gvalue.as_mut_ptr();
+            if bindings::vips_image_get(
+                self.image
+                    .ctx,
+                name.as_ptr(),
+                gvalue_ptr,
+            ) != 0
+            {
+                return Err(Error::IOError("Cannot get value".to_string()).extend());
+            }
+
+            let value = meta_value(
+                self,
+                name.as_ptr(),
+                gvalue_ptr,
+            );
+            bindings::g_value_unset(gvalue_ptr);
+            value
+        }
+    }
+
+    /// Gets the metadata item under the name converted to a string, as `vipsheader -a` prints it.
+    pub fn get_as_string(&self, name: impl AsRef<[u8]>) -> Result<String> {
+        unsafe {
+            let name = ensure_null_terminated(name)?;
+            let mut out: *mut gchar = std::ptr::null_mut();
+            let res = bindings::vips_image_get_as_string(
+                self.image
+                    .ctx,
+                name.as_ptr(),
+                &mut out,
+            );
+            crate::utils::safe_result(
+                res,
+                || {
+                    let string = new_string_from_ptr(out);
+                    bindings::g_free(out as *mut c_void);
+                    string
+                },
+                Error::IOError("Cannot get value as string".to_string()),
+            )
+        }
+    }
+}
+
+/// A Rust type which can be stored as a metadata item. Used by [`VipsImage::get()`] and [`VipsImage::set()`].
+pub trait MetaType: Sized {
+    /// Returns true if a metadata item of the GType can be read as this type.
+    fn accepts(gtype: usize) -> Result<bool>;
+    /// Reads the metadata item under the name.
+    fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self>;
+    /// Attaches the value as a metadata item under the name.
+    fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()>;
+}
+
+impl MetaType for i32 {
+    fn accepts(gtype: usize) -> Result<bool> {
+        Ok(gtype == get_g_type(G_TYPE_INT)?)
+    }
+    fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self> {
+        image.get_int(name.to_bytes())
+    }
+    fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()> {
+        image.set_int(
+            name.to_bytes(),
+            *self,
+        )
+    }
+}
+
+impl MetaType for f64 {
+    fn accepts(gtype: usize) -> Result<bool> {
+        Ok(gtype == get_g_type(G_TYPE_DOUBLE)?)
+    }
+    fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self> {
+        image.get_double(name.to_bytes())
+    }
+    fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()> {
+        image.set_double(
+            name.to_bytes(),
+            *self,
+        )
+    }
+}
+
+impl MetaType for String {
+    fn accepts(gtype: usize) -> Result<bool> {
+        Ok(
+            gtype == get_g_type(G_TYPE_STRING)?
+                || gtype == unsafe { bindings::vips_ref_string_get_type() },
+        )
+    }
+    fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self> {
+        image.get_string(name.to_bytes())
+    }
+    fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()> {
+        image.set_string(
+            name.to_bytes(),
+            self,
+        )
+    }
+}
+
+impl MetaType for Vec<u8> {
+    fn accepts(gtype: usize) -> Result<bool> {
+        Ok(gtype == unsafe { bindings::vips_blob_get_type() })
+    }
+    fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self> {
+        image.get_blob(name.to_bytes())
+    }
+    fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()> {
+        image.set_blob_copy(
+            name.to_bytes(),
+            self,
+        )
+    }
+}
+
+impl MetaType for Vec<i32> {
+    fn accepts(gtype: usize) -> Result<bool> {
+        Ok(gtype == unsafe { bindings::vips_array_int_get_type() })
+    }
+    fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self> {
+        image.get_array_int(name.to_bytes())
+    }
+    fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()> {
+        image.set_array_int(
+            name.to_bytes(),
+            self,
+        )
+    }
+}
+
+impl MetaType for Vec<f64> {
+    fn accepts(gtype: usize) -> Result<bool> {
+        Ok(gtype == unsafe { bindings::vips_array_double_get_type() })
+    }
+    fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self> {
+        image.get_array_double(name.to_bytes())
+    }
+    fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()> {
+        image.set_array_double(
+            name.to_bytes(),
+            self,
+        )
+    }
+}
+
+impl MetaType for VipsImage {
+    fn accepts(gtype: usize) -> Result<bool> {
+        Ok(unsafe {
+            bindings::g_type_is_a(
+                gtype,
+                bindings::vips_image_get_type(),
+            )
+        } != 0)
+    }
+    fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self> {
+        image.get_image(name.to_bytes())
+    }
+    fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()> {
+        image.set_image(
+            name.to_bytes(),
+            self,
+        )
+    }
+}
+
+fn get_enum<T: FromPrimitive>(image: &VipsImage, name: &CStr) -> Result<T> {
+    unsafe {
+        let mut gvalue = MaybeUninit::<GValue>::zeroed();
+        let gvalue_ptr = gvalue.as_mut_ptr();
+        if bindings::vips_image_get(
+            image
+                .image
+                .ctx,
+            name.as_ptr(),
+            gvalue_ptr,
+        ) != 0
+        {
+            return Err(Error::IOError("Cannot get enum".to_string()).extend());
+        }
+        let value = bindings::g_value_get_enum(gvalue_ptr);
+        bindings::g_value_unset(gvalue_ptr);
+        FromPrimitive::from_i32(value).ok_or(
+            Error::TypeMismatch(format!(
+                "{} is not a valid enum value",
+                value
+            )),
+        )
+    }
+}
+
+fn set_enum(image: &mut VipsImage, name: &CStr, gtype: usize, value: i32) -> Result<()> {
+    unsafe {
+        let mut gvalue = MaybeUninit::<GValue>::zeroed();
+        let gvalue_ptr = gvalue.as_mut_ptr();
+        bindings::g_value_init(
+            gvalue_ptr,
+            gtype,
+        );
+        bindings::g_value_set_enum(
+            gvalue_ptr,
+            value,
+        );
+        bindings::vips_image_set(
+            image
+                .image
+                .ctx,
+            name.as_ptr(),
+            gvalue_ptr,
+        );
+        bindings::g_value_unset(gvalue_ptr);
+        Ok(())
+    }
+}
+
+// Flag types such as `ForeignKeep` and `ForeignPngFilter` are left out, as combined values are not variants of the Rust enum.
+macro_rules! impl_meta_enum {
+    ($($enum_type:ty => $get_type:ident),* $(,)?) => {
+        $(
+            impl MetaType for $enum_type {
+                fn accepts(gtype: usize) -> Result<bool> {
+                    Ok(gtype == unsafe { bindings::$get_type() })
+                }
+                fn get_meta(image: &VipsImage, name: &CStr) -> Result<Self> {
+                    get_enum(image, name)
+                }
+                fn set_meta(&self, image: &mut VipsImage, name: &CStr) -> Result<()> {
+                    set_enum(image, name, unsafe { bindings::$get_type() }, *self as i32)
+                }
+            }
+        )*
+    };
+}
+
+impl_meta_enum! {
+    Access => vips_access_get_type,
+    Align => vips_align_get_type,
+    Angle => vips_angle_get_type,
+    Angle45 => vips_angle45_get_type,
+    BandFormat => vips_band_format_get_type,
+    BlendMode => vips_blend_mode_get_type,
+    Coding => vips_coding_get_type,
+    Combine => vips_combine_get_type,
+    CombineMode => vips_combine_mode_get_type,
+    CompassDirection => vips_compass_direction_get_type,
+    Direction => vips_direction_get_type,
+    Extend => vips_extend_get_type,
+    FailOn => vips_fail_on_get_type,
+    ForeignDzContainer => vips_foreign_dz_container_get_type,
+    ForeignDzDepth => vips_foreign_dz_depth_get_type,
+    ForeignDzLayout => vips_foreign_dz_layout_get_type,
+    ForeignHeifCompression => vips_foreign_heif_compression_get_type,
+    ForeignHeifEncoder => vips_foreign_heif_encoder_get_type,
+    ForeignPdfPageBox => vips_foreign_pdf_page_box_get_type,
+    ForeignPpmFormat => vips_foreign_ppm_format_get_type,
+    ForeignSubsample => vips_foreign_subsample_get_type,
+    ForeignTiffCompression => vips_foreign_tiff_compression_get_type,
+    ForeignTiffPredictor => vips_foreign_tiff_predictor_get_type,
+    ForeignTiffResunit => vips_foreign_tiff_resunit_get_type,
+    ForeignWebpPreset => vips_foreign_webp_preset_get_type,
+    Intent => vips_intent_get_type,
+    Interesting => vips_interesting_get_type,
+    Interpretation => vips_interpretation_get_type,
+    Kernel => vips_kernel_get_type,
+    OperationBoolean => vips_operation_boolean_get_type,
+    OperationComplex => vips_operation_complex_get_type,
+    OperationComplex2 => vips_operation_complex2_get_type,
+    OperationComplexget => vips_operation_complexget_get_type,
+    OperationMath => vips_operation_math_get_type,
+    OperationMath2 => vips_operation_math2_get_type,
+    OperationMorphology => vips_operation_morphology_get_type,
+    OperationRelational => vips_operation_relational_get_type,
+    OperationRound => vips_operation_round_get_type,
+    PCS => vips_pcs_get_type,
+    Precision => vips_precision_get_type,
+    RegionShrink => vips_region_shrink_get_type,
+    SdfShape => vips_sdf_shape_get_type,
+    Size => vips_size_get_type,
+    TextWrap => vips_text_wrap_get_type,
+}
+
+impl VipsImage {
+    /// Gets the metadata item under the name as `T`.
+    /// Returns `Error::TypeMismatch` if the item is stored as another type instead of converting it.
+    pub fn get<T: MetaType>(&self, name: impl AsRef<[u8]>) -> Result<T> {
+        let name = ensure_null_terminated(name)?;
+        let gtype = self.get_typeof(name.as_bytes())?;
+        if gtype == 0 {
+            return Err(
+                Error::IOError(format!(
+                    "No metadata item named {}",
+                    name.to_string_lossy()
+                )),
+            );
+        }
+        if !T::accepts(gtype)? {
+            return Err(
+                Error::TypeMismatch(format!(
+                    "{} is stored as {}",
+                    name.to_string_lossy(),
+                    new_string_from_ptr(unsafe { bindings::g_type_name(gtype) })
+                )),
+            );
+        }
+        T::get_meta(
+            self, &name,
+        )
+    }
+
+    /// Attaches the value as a metadata item under the name.
+    pub fn set<T: MetaType>(&mut self, name: impl AsRef<[u8]>, value: T) -> Result<()> {
+        let name = ensure_null_terminated(name)?;
+        value.set_meta(
+            self, &name,
+        )
+    }
+}
@@ -1756,8 +1756,8 @@ impl VipsImage {
     ///
     /// inp: `&[VipsImage]` -> Array of input images
     ///
-    /// mode: `&[i32]` -> Array of VipsBlendMode to join with
-    pub fn composite(inp: &[VipsImage], mode: &[i32]) -> Result<VipsImage> {
+    /// mode: `&[BlendMode]` -> Array of VipsBlendMode to join with
+    pub fn composite(inp: &[VipsImage], mode: &[BlendMode]) -> Result<VipsImage> {
         let mut out_out = VipsImage::from(null_mut());
         let vips_op_response = call(
             "composite",
@@ -1784,7 +1784,7 @@ impl VipsImage {
     ///
     /// inp: `&[VipsImage]` -> Array of input images
     ///
-    /// mode: `&[i32]` -> Array of VipsBlendMode to join with
+    /// mode: `&[BlendMode]` -> Array of VipsBlendMode to join with
     ///
     /// <ins>Optional arguments</ins>
     ///
@@ -1797,7 +1797,7 @@ impl VipsImage {
     /// premultiplied: `bool` -> Images have premultiplied alpha
     pub fn composite_with_opts(
         inp: &[VipsImage],
-        mode: &[i32],
+        mode: &[BlendMode],
         option: VOption,
     ) -> Result<VipsImage> {
         let mut out_out = VipsImage::from(null_mut());
@@ -0,0 +1,261 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    bindings,
+    enums::{BlendMode, CompassDirection, Extend},
+    error::Error,
+    voption::{Setter, VOption},
+    Result, VipsImage,
+};
+
+/// Where and how `VipsImage::overlay` places an overlay such as a watermark
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Placement {
+    gravity: CompassDirection,
+    margin_x: i32,
+    margin_y: i32,
+    relative_width: Option<f64>,
+    tile: bool,
+    opacity: f64,
+    mode: BlendMode,
+}
+
+impl Placement {
+    /// Places the overlay against an edge, a corner or the centre of the image.
+    pub fn new(gravity: CompassDirection) -> Placement {
+        Placement {
+            gravity,
+            margin_x: 0,
+            margin_y: 0,
+            relative_width: None,
+            tile: false,
+            opacity: 1.0,
+            mode: BlendMode::Over,
+        }
+    }
+
+    /// Sets the distance from the edges. When tiling, it is the gap between tiles, and a negative gap
+    /// which is as large as the overlay is an error.
+    pub fn margin(mut self, x: i32, y: i32) -> Placement {
+        self.margin_x = x;
+        self.margin_y = y;
+        self
+    }
+
+    /// Scales the overlay to a fraction of the image width, e.g. `0.15` for 15%.
+    pub fn relative_width(mut self, fraction: f64) -> Placement {
+        self.relative_width = Some(fraction);
+        self
+    }
+
+    /// Repeats the overlay across the whole image. Gravity is not used when tiling.
+    pub fn tile(mut self, tile: bool) -> Placement {
+        self.tile = tile;
+        self
+    }
+
+    /// Sets the opacity of the overlay, from 0.0 to 1.0.
+    pub fn opacity(mut self, opacity: f64) -> Placement {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the blend mode. Defaults to `BlendMode::Over`.
+    pub fn mode(mut self, mode: BlendMode) -> Placement {
+        self.mode = mode;
+        self
+    }
+
+    /// Position of the top-left corner of an overlay of the size in an image of the size
+    fn position(
+        &self,
+        width: i32,
+        height: i32,
+        overlay_width: i32,
+        overlay_height: i32,
+    ) -> (i32, i32) {
+        let left = self.margin_x;
+        let centre = (width - overlay_width) / 2;
+        let right = width - overlay_width - self.margin_x;
+        let top = self.margin_y;
+        let middle = (height - overlay_height) / 2;
+        let bottom = height - overlay_height - self.margin_y;
+        match self.gravity {
+            CompassDirection::Centre => (
+                centre,
+                middle,
+            ),
+            CompassDirection::North => (
+                centre,
+                top,
+            ),
+            CompassDirection::East => (
+                right,
+                middle,
+            ),
+            CompassDirection::South => (
+                centre,
+                bottom,
+            ),
+            CompassDirection::West => (
+                left,
+                middle,
+            ),
+            CompassDirection::NorthEast => (right, top),
+            CompassDirection::SouthEast => (
+                right,
+                bottom,
+            ),
+            CompassDirection::SouthWest => (
+                left,
+                bottom,
+            ),
+            CompassDirection::NorthWest => (left, top),
+        }
+    }
+}
+
+impl VipsImage {
+    /// Composites `overlay` onto the image as described by the placement.
+    ///
+    /// An alpha band is added to the overlay if it has none, and scaling is done on premultiplied pixels
+    /// so that transparent edges stay clean. The result has an alpha band only if the image had one.
+    ///
+    /// `composite2` works in sRGB, so the result is converted back to the interpretation of the image,
+    /// e.g. to grey or CMYK. Images whose interpretation `colourspace` does not support come back as sRGB.
+    ///
+    /// ```no_run
+    /// use rs_vips::{enums::CompassDirection, Placement, VipsImage};
+    ///
+    /// let photo = VipsImage::new_from_file("photo.jpg").unwrap();
+    /// let logo = VipsImage::new_from_file("logo.png").unwrap();
+    /// let out = photo
+    ///     .overlay(
+    ///         &logo,
+    ///         Placement::new(CompassDirection::SouthEast)
+    ///             .margin(20, 20)
+    ///             .relative_width(0.15)
+    ///             .opacity(0.6),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn overlay(&self, overlay: &VipsImage, placement: Placement) -> Result<VipsImage> {
+        let mut overlay = if overlay.hasalpha() { overlay.clone() } else { overlay.addalpha()? };
+        let format = overlay.get_format()?;
+
+        if let Some(fraction) = placement.relative_width {
+            let scale = self.get_width() as f64 * fraction / overlay.get_width() as f64;
+            overlay = overlay
+                .premultiply()?
+                .resize(scale)?
+                .unpremultiply()?
+                .cast(format)?;
+        }
+
+        if placement.opacity < 1.0 {
+            let mut a = vec![1.0; overlay.get_bands() as usize];
+            if let Some(alpha) = a.last_mut() {
+                *alpha = placement.opacity;
+            }
+            overlay = overlay
+                .linear(&a, &[0.0])?
+                .cast(format)?;
+        }
+
+        let (width, height) = (
+            self.get_width(),
+            self.get_height(),
+        );
+        let transparent = vec![0.0; overlay.get_bands() as usize];
+        let layer = if placement.tile {
+            let cell_width = overlay.get_width() + placement.margin_x;
+            let cell_height = overlay.get_height() + placement.margin_y;
+            if cell_width <= 0 || cell_height <= 0 {
+                return Err(
+                    Error::OperationError(format!(
+                        "Tile margin {}x{} leaves no room for a {}x{} overlay",
+                        placement.margin_x,
+                        placement.margin_y,
+                        overlay.get_width(),
+                        overlay.get_height()
+                    )),
+                );
+            }
+            overlay
+                .embed_with_opts(
+                    0,
+                    0,
+                    cell_width,
+                    cell_height,
+                    VOption::new()
+                        .set(
+                            "extend",
+                            Extend::Background as i32,
+                        )
+                        .set(
+                            "background",
+                            transparent.as_slice(),
+                        ),
+                )?
+                .replicate(
+                    (width + cell_width - 1) / cell_width,
+                    (height + cell_height - 1) / cell_height,
+                )?
+                .extract_area(
+                    0,
+                    0,
+                    width,
+                    height,
+                )?
+        } else {
+            let (x, y) = placement.position(
+                width,
+                height,
+                overlay.get_width(),
+                overlay.get_height(),
+            );
+            overlay.embed_with_opts(
+                x,
+                y,
+                width,
+                height,
+                VOption::new()
+                    .set(
+                        "extend",
+                        Extend::Background as i32,
+                    )
+                    .set(
+                        "background",
+                        transparent.as_slice(),
+                    ),
+            )?
+        };
+
+        let base = if self.hasalpha() { self.clone() } else { self.addalpha()? };
+        let out = base.composite2(
+            &layer,
+            placement.mode,
+        )?;
+        let interpretation = self.guess_interpretation()?;
+        let supported = unsafe {
+            bindings::vips_colourspace_issupported(
+                self.image
+                    .ctx,
+            ) != 0
+        };
+        let out = if supported && out.get_interpretation()? != interpretation {
+            out.colourspace(interpretation)?
+        } else {
+            out
+        };
+        if self.hasalpha() {
+            return Ok(out);
+        }
+        out.extract_band_with_opts(
+            0,
+            VOption::new().set(
+                "n",
+                out.get_bands() - 1,
+            ),
+        )
+    }
+}
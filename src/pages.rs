@@ -0,0 +1,342 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    enums::Extend,
+    error::Error,
+    voption::{Setter, VOption},
+    Animation, Result, VipsImage,
+};
+use std::path::{Path, PathBuf};
+
+enum PageInput<'a> {
+    File(PathBuf),
+    Buffer(&'a [u8]),
+}
+
+/// Iterator over the pages of a multi-page document such as PDF, TIFF, HEIF or GIF.
+/// Each page is loaded on demand with the `page` and `n` options of the loader.
+pub struct Pages<'a> {
+    input: PageInput<'a>,
+    n_pages: i32,
+    next: i32,
+}
+
+impl VipsImage {
+    /// Returns an iterator which loads the pages of the file one at a time.
+    ///
+    /// ```no_run
+    /// use rs_vips::VipsImage;
+    ///
+    /// for page in VipsImage::pages_from_file("document.pdf").unwrap() {
+    ///     let thumbnail = page.unwrap().thumbnail_image(256).unwrap();
+    ///     println!("{}x{}", thumbnail.get_width(), thumbnail.get_height());
+    /// }
+    /// ```
+    pub fn pages_from_file<P: AsRef<Path>>(filename: P) -> Result<Pages<'static>> {
+        Pages::new(
+            PageInput::File(
+                filename
+                    .as_ref()
+                    .to_path_buf(),
+            ),
+        )
+    }
+
+    /// Returns an iterator which loads the pages of the formatted buffer one at a time.
+    pub fn pages_from_buffer(buffer: &[u8]) -> Result<Pages<'_>> {
+        Pages::new(PageInput::Buffer(buffer))
+    }
+}
+
+impl<'a> Pages<'a> {
+    fn new(input: PageInput<'a>) -> Result<Pages<'a>> {
+        // Only the header is read here; pixels are decoded when the page is used
+        let first = match &input {
+            PageInput::File(filename) => VipsImage::new_from_file(filename)?,
+            PageInput::Buffer(buffer) => VipsImage::new_from_buffer(buffer, "")?,
+        };
+        Ok(Pages {
+            input,
+            n_pages: first.get_n_pages(),
+            next: 0,
+        })
+    }
+
+    /// Total number of pages in the document, from `n-pages`
+    pub fn n_pages(&self) -> i32 {
+        self.n_pages
+    }
+
+    /// Loads the page at the index.
+    pub fn page(&self, index: i32) -> Result<VipsImage> {
+        if index < 0 || index >= self.n_pages {
+            return Err(
+                Error::OperationError(format!(
+                    "Page {} out of range",
+                    index
+                )),
+            );
+        }
+        // Single-page formats such as JPEG have no `page` option
+        let option = if self.n_pages > 1 {
+            VOption::new()
+                .set(
+                    "page",
+                    index,
+                )
+                .set("n", 1)
+        } else {
+            VOption::new()
+        };
+        match &self.input {
+            PageInput::File(filename) => VipsImage::new_from_file_with_opts(
+                filename,
+                option,
+            ),
+            PageInput::Buffer(buffer) => VipsImage::new_from_buffer_with_opts(
+                buffer,
+                "",
+                option,
+            ),
+        }
+    }
+}
+
+impl Iterator for Pages<'_> {
+    type Item = Result<VipsImage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.n_pages {
+            return None;
+        }
+        let page = self.page(self.next);
+        self.next += 1;
+        Some(page)
+    }
+
+    fn size_hint(
+        &self,
+    ) -> (
+        usize,
+        Option<usize>,
+    ) {
+        let remaining = (self.n_pages - self.next).max(0) as usize;
+        (
+            remaining,
+            Some(remaining),
+        )
+    }
+}
+
+impl ExactSizeIterator for Pages<'_> {}
+
+/// Frame delay for pages added without one, in milliseconds
+const DEFAULT_DELAY: i32 = 100;
+
+/// How `PageWriter` brings pages to the common page size
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PageFit {
+    /// Centres each page on the background without scaling
+    Embed,
+    /// Scales each page to fit, keeping its aspect ratio, and centres it on the background
+    Resize,
+}
+
+/// Writes a sequence of pages, possibly of different sizes, as a multi-page TIFF or an animated WebP or GIF.
+///
+/// ```no_run
+/// use rs_vips::{PageWriter, VipsImage};
+///
+/// let mut writer = PageWriter::new().background(&[255.0]);
+/// for page in VipsImage::pages_from_file("document.pdf").unwrap() {
+///     writer = writer.page(page.unwrap());
+/// }
+/// writer
+///     .tiffsave("document.tif")
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct PageWriter {
+    pages: Vec<VipsImage>,
+    delays: Vec<i32>,
+    fit: PageFit,
+    background: Vec<f64>,
+    loop_count: i32,
+}
+
+impl Default for PageWriter {
+    fn default() -> Self {
+        PageWriter {
+            pages: Vec::new(),
+            delays: Vec::new(),
+            fit: PageFit::Embed,
+            background: vec![0.0],
+            loop_count: 0,
+        }
+    }
+}
+
+impl PageWriter {
+    pub fn new() -> PageWriter {
+        PageWriter::default()
+    }
+
+    /// Sets how pages are brought to the common size. Defaults to `PageFit::Embed`.
+    pub fn fit(mut self, fit: PageFit) -> PageWriter {
+        self.fit = fit;
+        self
+    }
+
+    /// Sets the colour of the area around smaller pages. Defaults to black.
+    pub fn background(mut self, background: &[f64]) -> PageWriter {
+        self.background = background.to_vec();
+        self
+    }
+
+    /// Sets the number of times an animation repeats, 0 meaning forever.
+    pub fn loop_count(mut self, loop_count: i32) -> PageWriter {
+        self.loop_count = loop_count;
+        self
+    }
+
+    /// Adds a page shown for the default delay of 100 ms in animations.
+    pub fn page(self, page: VipsImage) -> PageWriter {
+        self.page_with_delay(
+            page,
+            DEFAULT_DELAY,
+        )
+    }
+
+    /// Adds a page shown for `delay` milliseconds in animations.
+    pub fn page_with_delay(mut self, page: VipsImage, delay: i32) -> PageWriter {
+        self.pages
+            .push(page);
+        self.delays
+            .push(delay);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.pages
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages
+            .is_empty()
+    }
+
+    fn normalize(&self, page: &VipsImage, width: i32, height: i32) -> Result<VipsImage> {
+        let page = match self.fit {
+            PageFit::Resize if page.get_width() != width || page.get_height() != height => {
+                let scale = (width as f64 / page.get_width() as f64)
+                    .min(height as f64 / page.get_height() as f64);
+                page.resize(scale)?
+            }
+            _ => page.clone(),
+        };
+        if page.get_width() == width && page.get_height() == height {
+            return Ok(page);
+        }
+        page.embed_with_opts(
+            (width - page.get_width()) / 2,
+            (height - page.get_height()) / 2,
+            width,
+            height,
+            VOption::new()
+                .set(
+                    "extend",
+                    Extend::Background as i32,
+                )
+                .set(
+                    "background",
+                    self.background
+                        .as_slice(),
+                ),
+        )
+    }
+
+    /// Returns the pages brought to a common size and stacked vertically, with `page-height`, `delay` and `loop` set.
+    pub fn build(&self) -> Result<VipsImage> {
+        if self
+            .pages
+            .is_empty()
+        {
+            return Err(Error::OperationError("PageWriter has no pages".to_string()));
+        }
+        let width = self
+            .pages
+            .iter()
+            .map(VipsImage::get_width)
+            .max()
+            .unwrap_or_default();
+        let height = self
+            .pages
+            .iter()
+            .map(VipsImage::get_height)
+            .max()
+            .unwrap_or_default();
+        let pages = self
+            .pages
+            .iter()
+            .map(|page| {
+                self.normalize(
+                    page,
+                    width,
+                    height,
+                )
+            })
+            .collect::<Result<Vec<VipsImage>>>()?;
+
+        let mut animation = Animation::assemble(
+            &pages,
+            &self.delays,
+        )?;
+        animation.set_loop_count(self.loop_count)?;
+        Ok(animation.into_image())
+    }
+
+    /// Writes the pages as a multi-page TIFF.
+    pub fn tiffsave(&self, filename: &str) -> Result<()> {
+        self.build()?
+            .tiffsave(filename)
+    }
+
+    /// Writes the pages as a multi-page TIFF with `tiffsave` options.
+    pub fn tiffsave_with_opts(&self, filename: &str, option: VOption) -> Result<()> {
+        self.build()?
+            .tiffsave_with_opts(
+                filename,
+                option,
+            )
+    }
+
+    /// Writes the pages as an animated WebP.
+    pub fn webpsave(&self, filename: &str) -> Result<()> {
+        self.build()?
+            .webpsave(filename)
+    }
+
+    /// Writes the pages as an animated WebP with `webpsave` options.
+    pub fn webpsave_with_opts(&self, filename: &str, option: VOption) -> Result<()> {
+        self.build()?
+            .webpsave_with_opts(
+                filename,
+                option,
+            )
+    }
+
+    /// Writes the pages as an animated GIF.
+    pub fn gifsave(&self, filename: &str) -> Result<()> {
+        self.build()?
+            .gifsave(filename)
+    }
+
+    /// Writes the pages as an animated GIF with `gifsave` options.
+    pub fn gifsave_with_opts(&self, filename: &str, option: VOption) -> Result<()> {
+        self.build()?
+            .gifsave_with_opts(
+                filename,
+                option,
+            )
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     bindings::{self, free},
+    context,
     error::Error,
     utils::{result_cond, safe_result_cond},
     Result, VipsImage,
@@ -13,6 +14,7 @@ pub struct VipsRegion {
 
 impl VipsRegion {
     pub fn new(image: &VipsImage) -> Result<VipsRegion> {
+        let _guard = context::enter()?;
         unsafe {
             let res = bindings::vips_region_new(
                 image
@@ -64,13 +66,6 @@ impl VipsRegion {
 
 impl Drop for VipsRegion {
     fn drop(&mut self) {
-        unsafe {
-            if !self
-                .ctx
-                .is_null()
-            {
-                bindings::g_object_unref(self.ctx as *mut c_void);
-            }
-        }
+        context::unref(self.ctx);
     }
 }
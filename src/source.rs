@@ -3,6 +3,7 @@
 use crate::{
     bindings::{self, gint64},
     connection::VipsConnection,
+    context,
     error::Error,
     utils::{self, new_c_string, path_to_cstring, result_cond, safe_result_cond},
     Result,
@@ -29,6 +30,7 @@ struct SourceContext {
 impl VipsSource {
     /// Create a target from a reader.
     pub fn new_from_reader<R: Reader>(input: R) -> Result<VipsSource> {
+        let _guard = context::enter()?;
         unsafe {
             let source = bindings::vips_source_custom_new();
 
@@ -94,6 +96,7 @@ impl VipsSource {
 
     /// Create an source attached to a file descriptor. descriptor is closed with close() when source is finalized.
     pub fn new_from_descriptor(descriptor: i32) -> Result<VipsSource> {
+        let _guard = context::enter()?;
         unsafe {
             let res = bindings::vips_source_new_from_descriptor(descriptor);
             result_cond(
@@ -110,6 +113,7 @@ impl VipsSource {
 
     /// Create a source attached to a file.
     pub fn new_from_file<P: AsRef<Path>>(filename: P) -> Result<VipsSource> {
+        let _guard = context::enter()?;
         unsafe {
             let filename_c_str = path_to_cstring(filename)?;
             let res = bindings::vips_source_new_from_file(filename_c_str.as_ptr());
@@ -125,6 +129,7 @@ impl VipsSource {
 
     /// Create a source attached to an area of memory. You must not free data while the source is active.
     pub fn new_from_memory(buffer: &[u8]) -> Result<VipsSource> {
+        let _guard = context::enter()?;
         unsafe {
             let res = bindings::vips_source_new_from_memory(
                 buffer.as_ptr() as *const c_void,
@@ -144,6 +149,7 @@ impl VipsSource {
 
     /// Create a source from an option string.
     pub fn new_from_options(option_str: &str) -> Result<VipsSource> {
+        let _guard = context::enter()?;
         unsafe {
             let options = new_c_string(option_str)?;
             let res = bindings::vips_source_new_from_options(options.as_ptr());
@@ -337,13 +343,6 @@ unsafe extern "C" fn source_destroy(data: *mut c_void, _: *mut bindings::_GClosu
 
 impl Drop for VipsSource {
     fn drop(&mut self) {
-        unsafe {
-            if !self
-                .ctx
-                .is_null()
-            {
-                bindings::g_object_unref(self.ctx as *mut c_void);
-            }
-        }
+        context::unref(self.ctx);
     }
 }
@@ -0,0 +1,82 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{error::Error, utils, Point, Result, VipsImage};
+
+/// Number of columns of the `stats` matrix
+const STATS_COLUMNS: usize = 10;
+
+/// Statistics of one band, or of all bands together
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct BandStats {
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+    /// Sum of squares
+    pub sum2: f64,
+    pub mean: f64,
+    /// Standard deviation
+    pub deviation: f64,
+    /// Position of a pixel with the minimum value
+    pub min_position: Point,
+    /// Position of a pixel with the maximum value
+    pub max_position: Point,
+}
+
+impl BandStats {
+    fn from_row(row: &[f64]) -> BandStats {
+        BandStats {
+            min: row[0],
+            max: row[1],
+            sum: row[2],
+            sum2: row[3],
+            mean: row[4],
+            deviation: row[5],
+            min_position: Point::new(
+                row[6] as i32,
+                row[7] as i32,
+            ),
+            max_position: Point::new(
+                row[8] as i32,
+                row[9] as i32,
+            ),
+        }
+    }
+}
+
+/// Statistics of an image, parsed from the matrix returned by `stats`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImageStats {
+    /// Statistics over all bands
+    pub all: BandStats,
+    /// Statistics of each band
+    pub bands: Vec<BandStats>,
+}
+
+impl VipsImage {
+    /// Computes min, max, sum, sum of squares, mean, deviation and the positions of min and max
+    /// for the whole image and for each band, with `stats`.
+    ///
+    /// ```no_run
+    /// use rs_vips::VipsImage;
+    ///
+    /// let image = VipsImage::new_from_file("photo.jpg").unwrap();
+    /// let statistics = image
+    ///     .statistics()
+    ///     .unwrap();
+    /// println!("mean {}, red max at {:?}", statistics.all.mean, statistics.bands[0].max_position);
+    /// ```
+    pub fn statistics(&self) -> Result<ImageStats> {
+        let values = utils::to_f64_vec(&self.stats()?)?;
+        let mut rows = values.chunks_exact(STATS_COLUMNS);
+        let Some(all) = rows.next() else {
+            return Err(Error::OperationError("Invalid stats matrix".to_string()));
+        };
+        Ok(
+            ImageStats {
+                all: BandStats::from_row(all),
+                bands: rows
+                    .map(BandStats::from_row)
+                    .collect(),
+            },
+        )
+    }
+}
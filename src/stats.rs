@@ -0,0 +1,88 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    bindings::{self, GTypeInstance, VipsObjectClass},
+    utils::new_string_from_ptr,
+};
+use std::{ffi::c_void, ptr::null_mut};
+
+/// A snapshot of the operation cache and the tracked resources of libvips
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct VipsStats {
+    /// Current number of operations in cache
+    pub cache_size: i32,
+    /// Maximum number of operations kept in cache
+    pub cache_max: i32,
+    /// Maximum amount of tracked memory before cached operations are dropped
+    pub cache_max_mem: usize,
+    /// Maximum number of tracked files before cached operations are dropped
+    pub cache_max_files: i32,
+    /// Number of bytes currently allocated via vips_tracked_malloc()
+    pub tracked_mem: usize,
+    /// Largest number of bytes simultaneously allocated via vips_tracked_malloc()
+    pub tracked_mem_highwater: usize,
+    /// Number of active allocations
+    pub tracked_allocs: i32,
+    /// Number of open files
+    pub tracked_files: i32,
+}
+
+impl VipsStats {
+    pub(crate) fn snapshot() -> VipsStats {
+        unsafe {
+            VipsStats {
+                cache_size: bindings::vips_cache_get_size(),
+                cache_max: bindings::vips_cache_get_max(),
+                cache_max_mem: bindings::vips_cache_get_max_mem(),
+                cache_max_files: bindings::vips_cache_get_max_files(),
+                tracked_mem: bindings::vips_tracked_get_mem(),
+                tracked_mem_highwater: bindings::vips_tracked_get_mem_highwater(),
+                tracked_allocs: bindings::vips_tracked_get_allocs(),
+                tracked_files: bindings::vips_tracked_get_files(),
+            }
+        }
+    }
+}
+
+/// An operation alive in libvips, cached or still running
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiveOperation {
+    /// Operation nickname, e.g. `resize`
+    pub nickname: String,
+    /// Operation description, e.g. `resize an image`
+    pub description: String,
+}
+
+/// Copies the class details of an operation while libvips holds the object table lock, which keeps the object
+/// from being freed. Nothing else of the object is read, as another thread may be disposing of it.
+unsafe extern "C" fn collect_operation(
+    item: *mut c_void,
+    a: *mut c_void,
+    _b: *mut c_void,
+) -> *mut c_void {
+    if bindings::g_type_check_instance_is_a(
+        item as *mut GTypeInstance,
+        bindings::vips_operation_get_type(),
+    ) != 0
+    {
+        let class = (*(item as *mut GTypeInstance)).g_class as *mut VipsObjectClass;
+        (*(a as *mut Vec<LiveOperation>)).push(
+            LiveOperation {
+                nickname: new_string_from_ptr((*class).nickname),
+                description: new_string_from_ptr((*class).description),
+            },
+        );
+    }
+    null_mut()
+}
+
+pub(crate) fn live_operations() -> Vec<LiveOperation> {
+    let mut operations: Vec<LiveOperation> = Vec::new();
+    unsafe {
+        bindings::vips_object_map(
+            Some(collect_operation),
+            &mut operations as *mut Vec<LiveOperation> as *mut c_void,
+            null_mut(),
+        );
+    }
+    operations
+}
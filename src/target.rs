@@ -3,6 +3,7 @@
 use crate::{
     bindings::{self, gint64},
     connection::VipsConnection,
+    context,
     error::Error,
     utils::{new_c_string, path_to_cstring, result_cond},
     Result,
@@ -28,6 +29,7 @@ struct TargetContext {
 impl VipsTarget {
     /// Create a target from a writer.
     pub fn new_to_writer<W: Writer>(output: W) -> Result<VipsTarget> {
+        let _guard = context::enter()?;
         unsafe {
             let target = bindings::vips_target_custom_new();
 
@@ -129,6 +131,7 @@ impl VipsTarget {
 
     /// Create a target attached to a file descriptor. descriptor is kept open until the target is finalized.
     pub fn new_to_descriptor(descriptor: i32) -> Result<VipsTarget> {
+        let _guard = context::enter()?;
         unsafe {
             let res = bindings::vips_target_new_to_descriptor(descriptor);
             result_cond(
@@ -145,6 +148,7 @@ impl VipsTarget {
 
     /// Create a target attached to a file.
     pub fn new_to_file<P: AsRef<Path>>(filename: P) -> Result<VipsTarget> {
+        let _guard = context::enter()?;
         unsafe {
             let filename_c_str = path_to_cstring(filename)?;
             let res = bindings::vips_target_new_to_file(filename_c_str.as_ptr());
@@ -160,6 +164,7 @@ impl VipsTarget {
 
     /// Create a target which will write to a memory area. Read from blob to get memory.
     pub fn new_to_memory() -> Result<VipsTarget> {
+        let _guard = context::enter()?;
         unsafe {
             let res = bindings::vips_target_new_to_memory();
             result_cond(
@@ -354,13 +359,6 @@ unsafe extern "C" fn on_end(_: *mut bindings::VipsTargetCustom, data: *mut c_voi
 
 impl Drop for VipsTarget {
     fn drop(&mut self) {
-        unsafe {
-            if !self
-                .ctx
-                .is_null()
-            {
-                bindings::g_object_unref(self.ctx as *mut c_void);
-            }
-        }
+        context::unref(self.ctx);
     }
 }
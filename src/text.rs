@@ -0,0 +1,363 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{
+    enums::{Align, Interpretation, TextWrap},
+    voption::{Setter, VOption},
+    Color, Result, VipsImage,
+};
+
+/// DPI `text` renders at when none is given
+const DEFAULT_DPI: i32 = 72;
+
+/// Font weight of a text span
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FontWeight {
+    Thin,
+    Light,
+    Normal,
+    Medium,
+    Bold,
+    Heavy,
+}
+
+impl FontWeight {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FontWeight::Thin => "ultralight",
+            FontWeight::Light => "light",
+            FontWeight::Normal => "normal",
+            FontWeight::Medium => "medium",
+            FontWeight::Bold => "bold",
+            FontWeight::Heavy => "heavy",
+        }
+    }
+}
+
+/// A run of text with its own style
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSpan {
+    text: String,
+    font: Option<String>,
+    color: Option<Color>,
+    weight: Option<FontWeight>,
+    italic: bool,
+    underline: bool,
+}
+
+impl TextSpan {
+    pub fn new(text: &str) -> TextSpan {
+        TextSpan {
+            text: text.to_string(),
+            font: None,
+            color: None,
+            weight: None,
+            italic: false,
+            underline: false,
+        }
+    }
+
+    /// Sets a Pango font description, e.g. `Serif 12`.
+    pub fn font(mut self, font: &str) -> TextSpan {
+        self.font = Some(font.to_string());
+        self
+    }
+
+    /// Sets the colour. It only shows when the text is rendered with `rgba`.
+    pub fn color(mut self, color: Color) -> TextSpan {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn weight(mut self, weight: FontWeight) -> TextSpan {
+        self.weight = Some(weight);
+        self
+    }
+
+    pub fn italic(mut self) -> TextSpan {
+        self.italic = true;
+        self
+    }
+
+    pub fn underline(mut self) -> TextSpan {
+        self.underline = true;
+        self
+    }
+
+    fn to_markup(&self) -> Result<String> {
+        let mut attributes = String::new();
+        if let Some(font) = &self.font {
+            attributes.push_str(&format!(
+                " font=\"{}\"",
+                escape_markup(font)
+            ));
+        }
+        if let Some(color) = &self.color {
+            let rgb = color.convert(Interpretation::Srgb)?;
+            let hex: String = rgb
+                .iter()
+                .take(3)
+                .map(|value| {
+                    format!(
+                        "{:02x}",
+                        value
+                            .round()
+                            .clamp(0.0, 255.0) as u8
+                    )
+                })
+                .collect();
+            attributes.push_str(&format!(
+                " foreground=\"#{}\"",
+                hex
+            ));
+            if let Some(alpha) = color.alpha() {
+                attributes.push_str(&format!(
+                    " fgalpha=\"{}%\"",
+                    (alpha * 100.0).round() as i32
+                ));
+            }
+        }
+        if let Some(weight) = self.weight {
+            attributes.push_str(&format!(
+                " weight=\"{}\"",
+                weight.as_str()
+            ));
+        }
+        if self.italic {
+            attributes.push_str(" style=\"italic\"");
+        }
+        if self.underline {
+            attributes.push_str(" underline=\"single\"");
+        }
+
+        let text = escape_markup(&self.text);
+        if attributes.is_empty() {
+            Ok(text)
+        } else {
+            Ok(format!(
+                "<span{}>{}</span>",
+                attributes, text
+            ))
+        }
+    }
+}
+
+/// Escapes text for use in Pango markup.
+pub fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Builds Pango markup from plain text and styled spans and renders it with `text`.
+///
+/// ```no_run
+/// use rs_vips::{Color, FontWeight, TextBuilder, TextSpan};
+///
+/// let (caption, dpi) = TextBuilder::new()
+///     .font("Sans")
+///     .span(
+///         TextSpan::new("Tom & Jerry")
+///             .weight(FontWeight::Bold)
+///             .color(Color::srgb8(200, 0, 0)),
+///     )
+///     .text(" at <5 years old")
+///     .fit(600, 80)
+///     .rgba(true)
+///     .render()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextBuilder {
+    spans: Vec<TextSpan>,
+    font: Option<String>,
+    fontfile: Option<String>,
+    width: Option<i32>,
+    height: Option<i32>,
+    align: Option<Align>,
+    justify: bool,
+    dpi: Option<i32>,
+    spacing: Option<i32>,
+    wrap: Option<TextWrap>,
+    rgba: bool,
+}
+
+impl TextBuilder {
+    pub fn new() -> TextBuilder {
+        TextBuilder::default()
+    }
+
+    /// Appends plain text, escaped so that `<` and `&` render as written.
+    pub fn text(mut self, text: &str) -> TextBuilder {
+        self.spans
+            .push(TextSpan::new(text));
+        self
+    }
+
+    /// Appends a styled span.
+    pub fn span(mut self, span: TextSpan) -> TextBuilder {
+        self.spans
+            .push(span);
+        self
+    }
+
+    /// Sets the Pango font description for the whole text, e.g. `Sans Bold 12`.
+    pub fn font(mut self, font: &str) -> TextBuilder {
+        self.font = Some(font.to_string());
+        self
+    }
+
+    /// Loads a font file so that its family can be used in `font`.
+    pub fn fontfile(mut self, fontfile: &str) -> TextBuilder {
+        self.fontfile = Some(fontfile.to_string());
+        self
+    }
+
+    /// Wraps lines at `width` pixels.
+    pub fn width(mut self, width: i32) -> TextBuilder {
+        self.width = Some(width);
+        self
+    }
+
+    /// Chooses the largest DPI at which the text fits in the box. `render` returns the DPI chosen.
+    pub fn fit(mut self, width: i32, height: i32) -> TextBuilder {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    pub fn align(mut self, align: Align) -> TextBuilder {
+        self.align = Some(align);
+        self
+    }
+
+    pub fn justify(mut self, justify: bool) -> TextBuilder {
+        self.justify = justify;
+        self
+    }
+
+    /// Sets the DPI to render at. It is ignored when fitting to a box.
+    pub fn dpi(mut self, dpi: i32) -> TextBuilder {
+        self.dpi = Some(dpi);
+        self
+    }
+
+    /// Sets the line spacing in points.
+    pub fn spacing(mut self, spacing: i32) -> TextBuilder {
+        self.spacing = Some(spacing);
+        self
+    }
+
+    pub fn wrap(mut self, wrap: TextWrap) -> TextBuilder {
+        self.wrap = Some(wrap);
+        self
+    }
+
+    /// Renders in colour as sRGB with alpha instead of a one-band mask.
+    pub fn rgba(mut self, rgba: bool) -> TextBuilder {
+        self.rgba = rgba;
+        self
+    }
+
+    /// The Pango markup of the text
+    pub fn markup(&self) -> Result<String> {
+        self.spans
+            .iter()
+            .map(TextSpan::to_markup)
+            .collect()
+    }
+
+    /// Renders the text, returning the image and the DPI it was rendered at.
+    pub fn render(
+        &self,
+    ) -> Result<(
+        VipsImage,
+        i32,
+    )> {
+        let markup = self.markup()?;
+        let fit = self
+            .width
+            .is_some()
+            && self
+                .height
+                .is_some();
+        let mut dpi = self
+            .dpi
+            .unwrap_or(DEFAULT_DPI);
+        let mut option = VOption::new()
+            .set(
+                "justify",
+                self.justify,
+            )
+            .set(
+                "rgba",
+                self.rgba,
+            );
+        if let Some(font) = &self.font {
+            option = option.set(
+                "font",
+                font.as_str(),
+            );
+        }
+        if let Some(fontfile) = &self.fontfile {
+            option = option.set(
+                "fontfile",
+                fontfile.as_str(),
+            );
+        }
+        if let Some(width) = self.width {
+            option = option.set(
+                "width",
+                width,
+            );
+        }
+        if let Some(height) = self.height {
+            option = option.set(
+                "height",
+                height,
+            );
+        }
+        if let Some(align) = self.align {
+            option = option.set(
+                "align",
+                align as i32,
+            );
+        }
+        if let (Some(dpi), false) = (
+            self.dpi,
+            fit,
+        ) {
+            option = option.set("dpi", dpi);
+        }
+        if let Some(spacing) = self.spacing {
+            option = option.set(
+                "spacing",
+                spacing,
+            );
+        }
+        if let Some(wrap) = self.wrap {
+            option = option.set(
+                "wrap",
+                wrap as i32,
+            );
+        }
+        if fit {
+            option = option.set(
+                "autofit_dpi",
+                &mut dpi,
+            );
+        }
+        let image = VipsImage::text_with_opts(
+            &markup,
+            option,
+        )?;
+        Ok((image, dpi))
+    }
+}
@@ -0,0 +1,102 @@
+// (c) Copyright 2025 mrdkprj
+use crate::VipsImage;
+use std::time::Instant;
+use tracing::{field::Empty, span::EnteredSpan, Span};
+
+/// A `tracing` span entered for the duration of a vips operation. The elapsed time is recorded when it is dropped.
+pub(crate) struct OperationSpan {
+    span: EnteredSpan,
+    start: Instant,
+}
+
+impl OperationSpan {
+    /// Span for a vips operation call. Savers evaluate the whole lazy pipeline, so they get a `vips_evaluate` span instead.
+    pub(crate) fn operation(
+        nickname: &str,
+        input: Option<&VipsImage>,
+        options: &[&str],
+    ) -> OperationSpan {
+        // Savers found by suffix are passed by type name, e.g. `VipsForeignSaveJpegFile`
+        let span = if nickname
+            .to_ascii_lowercase()
+            .contains("save")
+        {
+            tracing::info_span!(
+                "vips_evaluate",
+                operation = nickname,
+                width = Empty,
+                height = Empty,
+                bands = Empty,
+                options = ?options,
+                elapsed_us = Empty
+            )
+        } else {
+            tracing::info_span!(
+                "vips_operation",
+                operation = nickname,
+                width = Empty,
+                height = Empty,
+                bands = Empty,
+                options = ?options,
+                elapsed_us = Empty
+            )
+        };
+        Self::enter(
+            span, input,
+        )
+    }
+
+    /// Span for a method which evaluates the lazy pipeline of the image, such as `write_to_memory()`.
+    pub(crate) fn evaluation(name: &str, input: &VipsImage) -> OperationSpan {
+        let span = tracing::info_span!(
+            "vips_evaluate",
+            operation = name,
+            width = Empty,
+            height = Empty,
+            bands = Empty,
+            elapsed_us = Empty
+        );
+        Self::enter(
+            span,
+            Some(input),
+        )
+    }
+
+    fn enter(span: Span, input: Option<&VipsImage>) -> OperationSpan {
+        if let Some(image) = input.filter(|image| {
+            !image
+                .image
+                .ctx
+                .is_null()
+        }) {
+            span.record(
+                "width",
+                image.get_width(),
+            );
+            span.record(
+                "height",
+                image.get_height(),
+            );
+            span.record(
+                "bands",
+                image.get_bands(),
+            );
+        }
+        OperationSpan {
+            span: span.entered(),
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for OperationSpan {
+    fn drop(&mut self) {
+        self.span
+            .record(
+                "elapsed_us",
+                self.start
+                    .elapsed()
+                    .as_micros() as u64,
+            );
+    }
+}
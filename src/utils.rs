@@ -4,7 +4,12 @@ use crate::bindings::{
     self, g_log, g_type_from_name, vips_error_buffer, GLogLevelFlags_G_LOG_LEVEL_WARNING,
 };
 use crate::{error::Error, Image, Result, VipsImage};
-use std::{ffi::CString, path::Path, sync::Arc};
+use std::{
+    ffi::{c_char, c_void, CStr, CString},
+    path::Path,
+    ptr::null_mut,
+    sync::Arc,
+};
 
 pub(crate) fn vips_image_result(out: *mut bindings::VipsImage, err: Error) -> Result<VipsImage> {
     if out.is_null() {
@@ -83,6 +88,18 @@ pub(crate) fn new_c_string_from_raw(ptr: *mut i8) -> CString {
     unsafe { CString::from_raw(ptr) }
 }
 
+/// Copies a nul-terminated C string into a String, replacing invalid UTF-8. A null pointer gives an empty string.
+#[inline]
+pub(crate) fn new_string_from_ptr(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
 #[inline]
 pub(crate) fn path_to_cstring<P: AsRef<Path>>(path: P) -> Result<CString> {
     #[cfg(unix)]
@@ -161,6 +178,22 @@ pub(crate) const G_TYPE_UINT64: &str = "guint64";
 pub(crate) const G_TYPE_DOUBLE: &str = "gdouble";
 pub(crate) const G_TYPE_STRING: &str = "gchararray";
 
+/// Reads the pixels of the image as doubles, in band-interleaved pixel order.
+pub(crate) fn to_f64_vec(image: &VipsImage) -> Result<Vec<f64>> {
+    Ok(image
+        .cast(crate::enums::BandFormat::Double)?
+        .write_to_memory()
+        .chunks_exact(8)
+        .map(|bytes| {
+            f64::from_ne_bytes(
+                bytes
+                    .try_into()
+                    .unwrap_or_default(),
+            )
+        })
+        .collect())
+}
+
 pub(crate) fn get_g_type(name: &str) -> Result<usize> {
     let type_name = new_c_string(name)?;
     Ok(unsafe { g_type_from_name(type_name.as_ptr()) })
@@ -179,3 +212,113 @@ pub(crate) fn g_warning() -> Result<()> {
     };
     Ok(())
 }
+
+/// Returns the block state of the operation class found by type name or nickname, or None if there is no such operation.
+pub(crate) fn is_operation_blocked(name: *const c_char) -> Option<bool> {
+    unsafe {
+        let gtype = bindings::vips_type_find(
+            c"VipsOperation".as_ptr(),
+            name,
+        );
+        if gtype == 0 {
+            return None;
+        }
+        let class = bindings::g_type_class_ref(gtype) as *mut bindings::VipsOperationClass;
+        let blocked = (*class).flags & bindings::VipsOperationFlags_VIPS_OPERATION_BLOCKED != 0;
+        bindings::g_type_class_unref(class as *mut c_void);
+        Some(blocked)
+    }
+}
+
+pub(crate) enum LoadInput<'a> {
+    File(*const c_char),
+    Buffer(&'a [u8]),
+    Source(*mut bindings::VipsSource),
+}
+
+unsafe extern "C" fn blocked_loader_sub(
+    object_class: *mut bindings::VipsObjectClass,
+    a: *mut c_void,
+) -> *mut c_void {
+    let operation_class = object_class as *mut bindings::VipsOperationClass;
+    if (*operation_class).flags & bindings::VipsOperationFlags_VIPS_OPERATION_BLOCKED == 0 {
+        return null_mut();
+    }
+
+    let load_class = object_class as *mut bindings::VipsForeignLoadClass;
+    let is_a = match &*(a as *const LoadInput) {
+        LoadInput::File(filename) => (*load_class)
+            .is_a
+            .map(|is_a| is_a(*filename) != 0),
+        LoadInput::Buffer(buffer) => (*load_class)
+            .is_a_buffer
+            .map(
+                |is_a_buffer| {
+                    is_a_buffer(
+                        buffer.as_ptr() as *const c_void,
+                        buffer.len(),
+                    ) != 0
+                },
+            ),
+        LoadInput::Source(source) => (*load_class)
+            .is_a_source
+            .map(|is_a_source| is_a_source(*source) != 0),
+    };
+
+    if is_a.unwrap_or(false) {
+        object_class as *mut c_void
+    } else {
+        null_mut()
+    }
+}
+
+/// Finds a blocked loader which would have accepted the input. Use it to explain why no loader was found.
+///
+/// `vips_foreign_map` skips blocked classes, so the loader classes are walked by `vips_class_map_all` instead.
+pub(crate) fn find_blocked_loader(input: LoadInput) -> Option<String> {
+    unsafe {
+        let class = bindings::vips_class_map_all(
+            g_type_from_name(c"VipsForeignLoad".as_ptr()),
+            Some(blocked_loader_sub),
+            &input as *const LoadInput as *mut c_void,
+        ) as *mut bindings::VipsObjectClass;
+        if class.is_null() {
+            None
+        } else {
+            Some(new_string_from_ptr((*class).nickname))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, Vips, VipsImage};
+
+    #[test]
+    fn blocked_loader_is_reported() {
+        Vips::init("blocked_loader_is_reported").unwrap();
+
+        let path = std::env::temp_dir().join("rs-vips-blocked-loader.mat");
+        std::fs::write(
+            &path,
+            "2 2\n1 2\n3 4\n",
+        )
+        .unwrap();
+
+        Vips::block_web_unsafe(true).unwrap();
+        let blocked = VipsImage::new_from_file(&path);
+        Vips::block_web_unsafe(false).unwrap();
+        let unblocked = VipsImage::new_from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        match blocked {
+            Err(Error::Blocked(msg)) => assert!(msg.contains("matrixload")),
+            Err(e) => panic!(
+                "expected a blocked loader error, got {}",
+                e
+            ),
+            Ok(_) => panic!("expected a blocked loader error, got an image"),
+        }
+        assert!(unblocked.is_ok());
+    }
+}
@@ -9,20 +9,24 @@ use crate::{
         vips_array_image_get_type, vips_array_int_get_type, vips_blob_get_type,
         vips_cache_operation_buildp, vips_enum_from_nick, vips_error_clear, vips_image_get_type,
         vips_interpolate_get_type, vips_object_get_argument, vips_object_set_from_string,
-        vips_object_unref_outputs, vips_operation_new, vips_source_get_type, vips_target_get_type,
-        vips_value_get_array_double, vips_value_get_array_image, vips_value_get_blob,
-        vips_value_set_array_double, vips_value_set_array_image, vips_value_set_array_int,
-        vips_value_set_blob, GParamSpec, GTypeInstance, GValue, VipsArgumentClass,
-        VipsArgumentInstance, VipsImage, VipsObject, VipsOperation,
+        vips_object_unref_outputs, vips_operation_get_flags, vips_operation_new,
+        vips_source_get_type, vips_target_get_type, vips_value_get_array_double,
+        vips_value_get_array_image, vips_value_get_blob, vips_value_set_array_double,
+        vips_value_set_array_image, vips_value_set_array_int, vips_value_set_blob, GParamSpec,
+        GTypeInstance, GValue, VipsArgumentClass, VipsArgumentInstance, VipsImage, VipsObject,
+        VipsOperation, VipsOperationFlags_VIPS_OPERATION_BLOCKED,
     },
+    context,
+    error::Error,
     utils::{
         g_warning, get_g_type, new_c_string, G_TYPE_BOOLEAN, G_TYPE_DOUBLE, G_TYPE_INT,
         G_TYPE_STRING, G_TYPE_UINT64,
     },
     Result,
 };
+use num_traits::ToPrimitive;
 use std::{
-    ffi::{c_char, c_int, c_void},
+    ffi::{c_char, c_int, c_void, CStr},
     mem::MaybeUninit,
     sync::Arc,
 };
@@ -54,9 +58,39 @@ pub(crate) fn call_option_string_(
     option_string: *const c_char,
     option: VOption,
 ) -> Result<c_int> {
+    let _guard = context::enter()?;
+
+    let option = resolve_colors(
+        unsafe { CStr::from_ptr(operation) },
+        option,
+    )?;
+
+    #[cfg(feature = "tracing")]
+    let _span = operation_span(
+        operation,
+        &option,
+    );
+
     unsafe {
         let mut vips_operation = vips_operation_new(operation);
 
+        // Refuse blocked operations with a clear error rather than the generic build failure
+        if !vips_operation.is_null()
+            && vips_operation_get_flags(vips_operation) & VipsOperationFlags_VIPS_OPERATION_BLOCKED
+                != 0
+        {
+            let nickname = CStr::from_ptr((*(vips_operation as *mut VipsObject)).nickname)
+                .to_string_lossy()
+                .into_owned();
+            g_object_unref(vips_operation as *mut c_void);
+            return Err(
+                Error::Blocked(format!(
+                    "Operation {} is blocked",
+                    nickname
+                )),
+            );
+        }
+
         // Set option_string before set options
         if !option_string.is_null()
             && vips_object_set_from_string(
@@ -100,6 +134,67 @@ pub(crate) fn call_option_string_(
     }
 }
 
+#[cfg(feature = "tracing")]
+fn operation_span(operation: *const c_char, option: &VOption) -> crate::trace::OperationSpan {
+    let nickname = unsafe { CStr::from_ptr(operation) }.to_string_lossy();
+    let input = input_image(option);
+    let names: Vec<&str> = option
+        .options
+        .iter()
+        .map(|pair| {
+            pair.name
+                .as_str()
+        })
+        .collect();
+    crate::trace::OperationSpan::operation(
+        &nickname,
+        input,
+        &names,
+    )
+}
+
+/// The first input image of the operation
+fn input_image<'a>(option: &VOption<'a>) -> Option<&'a crate::VipsImage> {
+    option
+        .options
+        .iter()
+        .filter(|pair| pair.input)
+        .find_map(
+            |pair| match pair.value {
+                VipsValue::Image(image) => Some(image),
+                VipsValue::ImageArray(images) => images.first(),
+                _ => None,
+            },
+        )
+}
+
+/// Converts colour options to the pixel values of the first input image.
+fn resolve_colors<'a>(operation: &CStr, mut option: VOption<'a>) -> Result<VOption<'a>> {
+    let image = input_image(&option);
+    // flatten and savers remove the alpha band, so their background has the colour bands only
+    let operation = operation.to_string_lossy();
+    let colour_only = operation == "flatten" || operation.contains("save");
+    for pair in option
+        .options
+        .iter_mut()
+    {
+        if let VipsValue::Color(color) = pair.value {
+            let image = image.ok_or(
+                Error::OperationError(format!(
+                    "{} needs an input image to convert a colour",
+                    pair.name
+                )),
+            )?;
+            let mut ink = color.to_ink(image)?;
+            if colour_only && image.hasalpha() {
+                ink.pop();
+            }
+            pair.value = VipsValue::DoubleVec(ink);
+        }
+    }
+    Ok(option)
+}
+
 #[derive(Default)]
 struct ImageSource {
     buffer: Option<Arc<[u8]>>,
@@ -366,6 +461,17 @@ fn set_opreration(
                         value.len() as c_int,
                     );
                 }
+                VipsValue::EnumArray(ref value) => {
+                    g_value_init(
+                        gvalue_ptr,
+                        vips_array_int_get_type(),
+                    );
+                    vips_value_set_array_int(
+                        gvalue_ptr,
+                        value.as_ptr(),
+                        value.len() as c_int,
+                    );
+                }
                 VipsValue::DoubleArray(value) => {
                     g_value_init(
                         gvalue_ptr,
@@ -377,6 +483,17 @@ fn set_opreration(
                         value.len() as c_int,
                     );
                 }
+                VipsValue::DoubleVec(ref value) => {
+                    g_value_init(
+                        gvalue_ptr,
+                        vips_array_double_get_type(),
+                    );
+                    vips_value_set_array_double(
+                        gvalue_ptr,
+                        value.as_ptr(),
+                        value.len() as c_int,
+                    );
+                }
                 VipsValue::Image(value) => {
                     g_value_init(
                         gvalue_ptr,
@@ -562,7 +679,11 @@ enum VipsValue<'a> {
     Image(&'a crate::VipsImage),
     MutImage(&'a mut crate::VipsImage),
     IntArray(&'a [i32]),
+    EnumArray(Vec<i32>),
     DoubleArray(&'a [f64]),
+    DoubleVec(Vec<f64>),
+    /// Converted to `DoubleVec` for the input image before the call
+    Color(&'a crate::Color),
     MutDoubleArray(&'a mut Vec<f64>),
     ImageArray(&'a [crate::VipsImage]),
     Blob(&'a [u8]),
@@ -608,6 +729,27 @@ impl<'a> VOption<'a> {
             options: Vec::new(),
         }
     }
+
+    /// Removes the options with the name, so that it can be set again.
+    pub(crate) fn without(mut self, name: &str) -> Self {
+        self.options
+            .retain(|pair| pair.name != name);
+        self
+    }
+}
+
+/// Marker for the enumerations in [`crate::enums`], which libvips takes as int
+pub trait VipsEnum: ToPrimitive + Copy {}
+
+fn enum_array<T: VipsEnum>(values: &[T]) -> Vec<i32> {
+    values
+        .iter()
+        .map(|value| {
+            value
+                .to_i32()
+                .unwrap_or_default()
+        })
+        .collect()
 }
 
 /// Set the value of a name-value pair of VOption
@@ -844,6 +986,51 @@ impl<'a, const N: usize> Setter<'a, &'a [i32; N]> for VOption<'a> {
     }
 }
 
+// input &[enum]
+impl<'a, T: VipsEnum> Setter<'a, &'a [T]> for VOption<'a> {
+    fn set(mut self, name: &str, value: &'a [T]) -> VOption<'a> {
+        self.options
+            .push(
+                Pair::input(
+                    name,
+                    VipsValue::EnumArray(enum_array(value)),
+                ),
+            );
+        self
+    }
+    fn add(&mut self, name: &str, value: &'a [T]) {
+        self.options
+            .push(
+                Pair::input(
+                    name,
+                    VipsValue::EnumArray(enum_array(value)),
+                ),
+            );
+    }
+}
+
+impl<'a, T: VipsEnum, const N: usize> Setter<'a, &'a [T; N]> for VOption<'a> {
+    fn set(mut self, name: &str, value: &'a [T; N]) -> VOption<'a> {
+        self.options
+            .push(
+                Pair::input(
+                    name,
+                    VipsValue::EnumArray(enum_array(value)),
+                ),
+            );
+        self
+    }
+    fn add(&mut self, name: &str, value: &'a [T; N]) {
+        self.options
+            .push(
+                Pair::input(
+                    name,
+                    VipsValue::EnumArray(enum_array(value)),
+                ),
+            );
+    }
+}
+
 // input &[f64]
 impl<'a> Setter<'a, &'a [f64]> for VOption<'a> {
     fn set(mut self, name: &str, value: &'a [f64]) -> VOption<'a> {
@@ -889,6 +1076,29 @@ impl<'a, const N: usize> Setter<'a, &'a [f64; N]> for VOption<'a> {
     }
 }
 
+// input Color, converted to the pixel values of the input image like Color::to_ink
+impl<'a> Setter<'a, &'a crate::Color> for VOption<'a> {
+    fn set(mut self, name: &str, value: &'a crate::Color) -> VOption<'a> {
+        self.options
+            .push(
+                Pair::input(
+                    name,
+                    VipsValue::Color(value),
+                ),
+            );
+        self
+    }
+    fn add(&mut self, name: &str, value: &'a crate::Color) {
+        self.options
+            .push(
+                Pair::input(
+                    name,
+                    VipsValue::Color(value),
+                ),
+            );
+    }
+}
+
 // input &[VipsImage]
 impl<'a> Setter<'a, &'a [crate::VipsImage]> for VOption<'a> {
     fn set(mut self, name: &str, value: &'a [crate::VipsImage]) -> VOption<'a> {
@@ -0,0 +1,933 @@
+// (c) Copyright 2025 mrdkprj
+use crate::{error::Error, Result, VipsImage};
+
+pub(crate) const XMP_DATA: &str = "xmp-data";
+const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+/// Namespaces declared for these prefixes when a property uses one the packet did not declare
+const KNOWN_NAMESPACES: &[(&str, &str)] = &[
+    (
+        "dc",
+        "http://purl.org/dc/elements/1.1/",
+    ),
+    (
+        "xmp",
+        "http://ns.adobe.com/xap/1.0/",
+    ),
+    (
+        "xmpRights",
+        "http://ns.adobe.com/xap/1.0/rights/",
+    ),
+    (
+        "xmpMM",
+        "http://ns.adobe.com/xap/1.0/mm/",
+    ),
+    (
+        "photoshop",
+        "http://ns.adobe.com/photoshop/1.0/",
+    ),
+    (
+        "Iptc4xmpCore",
+        "http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/",
+    ),
+    (
+        "plus",
+        "http://ns.useplus.org/ldf/xmp/1.0/",
+    ),
+    (
+        "tiff",
+        "http://ns.adobe.com/tiff/1.0/",
+    ),
+    (
+        "exif",
+        "http://ns.adobe.com/exif/1.0/",
+    ),
+];
+
+/// Value of an XMP property
+#[derive(Clone, Debug, PartialEq)]
+pub enum XmpValue {
+    Text(String),
+    /// Ordered array, `rdf:Seq`
+    Seq(Vec<String>),
+    /// Unordered array, `rdf:Bag`
+    Bag(Vec<String>),
+    /// Language alternatives, `rdf:Alt`, as (language, value) pairs
+    Alt(
+        Vec<(
+            String,
+            String,
+        )>,
+    ),
+    /// Structured value kept as its XML, including the property element
+    Raw(String),
+}
+
+/// Properties of an `xmp-data` packet, keyed by qualified name such as `dc:creator`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Xmp {
+    /// `x:xmptk` of the packet, the toolkit which wrote it
+    toolkit: Option<String>,
+    /// `rdf:about` of the first `rdf:Description`
+    about: Option<String>,
+    namespaces: Vec<(
+        String,
+        String,
+    )>,
+    properties: Vec<(
+        String,
+        XmpValue,
+    )>,
+}
+
+impl Xmp {
+    pub fn new() -> Xmp {
+        Xmp::default()
+    }
+
+    /// Parses an XMP packet.
+    pub fn parse(data: &[u8]) -> Result<Xmp> {
+        let xml = std::str::from_utf8(data)
+            .map_err(|_| Error::IOError("XMP data is not UTF-8".to_string()))?
+            .trim_start_matches('\u{feff}');
+        let mut xmp = Xmp::default();
+        let mut tokenizer = Tokenizer {
+            xml,
+            pos: 0,
+        };
+        while let Some(token) = tokenizer.next()? {
+            if let Token::Start {
+                name,
+                attributes,
+                empty,
+            } = token
+            {
+                xmp.add_namespaces(&attributes);
+                let attribute = |wanted: &str| {
+                    attributes
+                        .iter()
+                        .find(|(name, _)| *name == wanted)
+                        .map(|(_, value)| value.clone())
+                };
+                if name == "x:xmpmeta" || name == "x:xapmeta" {
+                    xmp.toolkit = attribute("x:xmptk");
+                }
+                if name == "rdf:Description" {
+                    if xmp
+                        .about
+                        .is_none()
+                    {
+                        xmp.about = attribute("rdf:about");
+                    }
+                    for (name, value) in attributes {
+                        if !is_syntax_attribute(name) {
+                            xmp.set(
+                                name,
+                                XmpValue::Text(value),
+                            );
+                        }
+                    }
+                    if !empty {
+                        xmp.parse_properties(&mut tokenizer)?;
+                    }
+                }
+            }
+        }
+        Ok(xmp)
+    }
+
+    fn add_namespaces(
+        &mut self,
+        attributes: &[(
+            &str,
+            String,
+        )],
+    ) {
+        for (name, uri) in attributes {
+            if let Some(prefix) = name.strip_prefix("xmlns:") {
+                if !self
+                    .namespaces
+                    .iter()
+                    .any(|(known, _)| known == prefix)
+                {
+                    self.namespaces
+                        .push((
+                            prefix.to_string(),
+                            uri.clone(),
+                        ));
+                }
+            }
+        }
+    }
+
+    /// Reads the property elements of an `rdf:Description` up to its end tag.
+    fn parse_properties(&mut self, tokenizer: &mut Tokenizer) -> Result<()> {
+        loop {
+            let start = tokenizer.pos;
+            match tokenizer.next()? {
+                None | Some(Token::End(_)) => return Ok(()),
+                Some(Token::Text(_)) => {}
+                Some(Token::Start {
+                    name,
+                    attributes,
+                    empty,
+                }) => {
+                    self.add_namespaces(&attributes);
+                    let value = if empty {
+                        match attributes
+                            .iter()
+                            .find(|(name, _)| *name == "rdf:resource")
+                        {
+                            Some((_, resource)) => XmpValue::Text(resource.clone()),
+                            None if attributes
+                                .iter()
+                                .all(|(name, _)| is_syntax_attribute(name)) =>
+                            {
+                                XmpValue::Text(String::new())
+                            }
+                            None => XmpValue::Raw(tokenizer.xml[start..tokenizer.pos].to_string()),
+                        }
+                    } else {
+                        let mut children = Vec::new();
+                        let mut depth = 0;
+                        loop {
+                            let token = tokenizer
+                                .next()?
+                                .ok_or(Error::IOError("Unexpected end of XMP data".to_string()))?;
+                            match &token {
+                                Token::Start {
+                                    attributes,
+                                    empty,
+                                    ..
+                                } => {
+                                    self.add_namespaces(attributes);
+                                    if !empty {
+                                        depth += 1;
+                                    }
+                                }
+                                Token::End(_) if depth == 0 => break,
+                                Token::End(_) => depth -= 1,
+                                Token::Text(_) => {}
+                            }
+                            children.push(token);
+                        }
+                        interpret(&children).unwrap_or_else(|| {
+                            XmpValue::Raw(tokenizer.xml[start..tokenizer.pos].to_string())
+                        })
+                    };
+                    self.set(
+                        name, value,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the value of the property, e.g. `dc:rights`.
+    pub fn get(&self, name: &str) -> Option<&XmpValue> {
+        self.properties
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the property as text: the value of a simple property, the first item of an array,
+    /// or the `x-default` alternative.
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.get(name)? {
+            XmpValue::Text(text) => Some(text),
+            XmpValue::Seq(items) | XmpValue::Bag(items) => items
+                .first()
+                .map(String::as_str),
+            XmpValue::Alt(alternatives) => alternatives
+                .iter()
+                .find(|(lang, _)| lang == "x-default")
+                .or(alternatives.first())
+                .map(|(_, value)| value.as_str()),
+            XmpValue::Raw(_) => None,
+        }
+    }
+
+    /// All properties in document order
+    pub fn properties(
+        &self,
+    ) -> &[(
+        String,
+        XmpValue,
+    )] {
+        &self.properties
+    }
+
+    /// Sets the property, replacing any existing value.
+    pub fn set(&mut self, name: &str, value: XmpValue) {
+        match self
+            .properties
+            .iter_mut()
+            .find(|(key, _)| key == name)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self
+                .properties
+                .push((
+                    name.to_string(),
+                    value,
+                )),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<XmpValue> {
+        let index = self
+            .properties
+            .iter()
+            .position(|(key, _)| key == name)?;
+        Some(
+            self.properties
+                .remove(index)
+                .1,
+        )
+    }
+
+    /// Keeps only the properties for which `f` returns true.
+    pub fn retain<F: FnMut(&str, &XmpValue) -> bool>(&mut self, mut f: F) {
+        self.properties
+            .retain(
+                |(name, value)| {
+                    f(
+                        name, value,
+                    )
+                },
+            );
+    }
+
+    /// Sets `dc:creator`.
+    pub fn with_creator(mut self, creators: &[&str]) -> Xmp {
+        self.set(
+            "dc:creator",
+            XmpValue::Seq(
+                creators
+                    .iter()
+                    .map(|creator| creator.to_string())
+                    .collect(),
+            ),
+        );
+        self
+    }
+
+    /// Sets the copyright notice, `dc:rights`.
+    pub fn with_rights(mut self, rights: &str) -> Xmp {
+        self.set(
+            "dc:rights",
+            XmpValue::Alt(vec![(
+                "x-default".to_string(),
+                rights.to_string(),
+            )]),
+        );
+        self
+    }
+
+    /// Sets the rights usage terms, `xmpRights:UsageTerms`.
+    pub fn with_usage_terms(mut self, terms: &str) -> Xmp {
+        self.set(
+            "xmpRights:UsageTerms",
+            XmpValue::Alt(vec![(
+                "x-default".to_string(),
+                terms.to_string(),
+            )]),
+        );
+        self
+    }
+
+    /// Sets the URL of the copyright statement, `xmpRights:WebStatement`.
+    pub fn with_web_statement(mut self, url: &str) -> Xmp {
+        self.set(
+            "xmpRights:WebStatement",
+            XmpValue::Text(url.to_string()),
+        );
+        self
+    }
+
+    /// Marks the image as rights-managed or public domain, `xmpRights:Marked`.
+    pub fn with_marked(mut self, marked: bool) -> Xmp {
+        self.set(
+            "xmpRights:Marked",
+            XmpValue::Text(if marked { "True" } else { "False" }.to_string()),
+        );
+        self
+    }
+
+    /// Sets the credit line, `photoshop:Credit`.
+    pub fn with_credit(mut self, credit: &str) -> Xmp {
+        self.set(
+            "photoshop:Credit",
+            XmpValue::Text(credit.to_string()),
+        );
+        self
+    }
+
+    /// Serializes the properties as an XMP packet. The `x:xmptk` and `rdf:about` read are kept, and the properties
+    /// of all `rdf:Description` elements are written in a single one, which describes the same resource.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut namespaces: Vec<(&str, &str)> = Vec::new();
+        for (name, _) in &self.properties {
+            let Some((prefix, _)) = name.split_once(':') else {
+                continue;
+            };
+            if namespaces
+                .iter()
+                .any(|(known, _)| *known == prefix)
+            {
+                continue;
+            }
+            if let Some((_, uri)) = self
+                .namespaces
+                .iter()
+                .map(
+                    |(prefix, uri)| {
+                        (
+                            prefix.as_str(),
+                            uri.as_str(),
+                        )
+                    },
+                )
+                .chain(
+                    KNOWN_NAMESPACES
+                        .iter()
+                        .copied(),
+                )
+                .find(|(known, _)| *known == prefix)
+            {
+                namespaces.push((
+                    prefix,
+                    uri,
+                ));
+            }
+        }
+        // Raw values may use prefixes of their own
+        for (prefix, uri) in &self.namespaces {
+            if prefix != "rdf"
+                && prefix != "x"
+                && !namespaces
+                    .iter()
+                    .any(|(known, _)| known == prefix)
+            {
+                namespaces.push((
+                    prefix,
+                    uri,
+                ));
+            }
+        }
+
+        let mut xml =
+            String::from("<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n");
+        xml.push_str("<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"");
+        if let Some(toolkit) = &self.toolkit {
+            xml.push_str(&format!(
+                " x:xmptk=\"{}\"",
+                escape(toolkit)
+            ));
+        }
+        xml.push_str(">\n");
+        xml.push_str(&format!(
+            " <rdf:RDF xmlns:rdf=\"{}\">\n",
+            RDF_NS
+        ));
+        xml.push_str(&format!(
+            "  <rdf:Description rdf:about=\"{}\"",
+            escape(
+                self.about
+                    .as_deref()
+                    .unwrap_or_default()
+            )
+        ));
+        for (prefix, uri) in namespaces {
+            xml.push_str(&format!(
+                "\n    xmlns:{}=\"{}\"",
+                prefix,
+                escape(uri)
+            ));
+        }
+        xml.push_str(">\n");
+
+        for (name, value) in &self.properties {
+            let items = |kind: &str, items: &[String]| {
+                let mut xml = format!(
+                    "   <{}>\n    <rdf:{}>\n",
+                    name, kind
+                );
+                for item in items {
+                    xml.push_str(&format!(
+                        "     <rdf:li>{}</rdf:li>\n",
+                        escape(item)
+                    ));
+                }
+                xml.push_str(&format!(
+                    "    </rdf:{}>\n   </{}>\n",
+                    kind, name
+                ));
+                xml
+            };
+            match value {
+                XmpValue::Text(text) => xml.push_str(&format!(
+                    "   <{}>{}</{}>\n",
+                    name,
+                    escape(text),
+                    name
+                )),
+                XmpValue::Seq(values) => xml.push_str(&items(
+                    "Seq",
+                    values,
+                )),
+                XmpValue::Bag(values) => xml.push_str(&items(
+                    "Bag",
+                    values,
+                )),
+                XmpValue::Alt(alternatives) => {
+                    xml.push_str(&format!(
+                        "   <{}>\n    <rdf:Alt>\n",
+                        name
+                    ));
+                    for (lang, value) in alternatives {
+                        xml.push_str(&format!(
+                            "     <rdf:li xml:lang=\"{}\">{}</rdf:li>\n",
+                            escape(lang),
+                            escape(value)
+                        ));
+                    }
+                    xml.push_str(&format!(
+                        "    </rdf:Alt>\n   </{}>\n",
+                        name
+                    ));
+                }
+                XmpValue::Raw(raw) => {
+                    xml.push_str("   ");
+                    xml.push_str(raw);
+                    xml.push('\n');
+                }
+            }
+        }
+
+        xml.push_str("  </rdf:Description>\n </rdf:RDF>\n</x:xmpmeta>\n<?xpacket end=\"w\"?>");
+        xml.into_bytes()
+    }
+}
+
+/// Reads a property value consisting of text or of an `rdf:Seq`, `rdf:Bag` or `rdf:Alt` of text items.
+fn interpret(children: &[Token]) -> Option<XmpValue> {
+    let tokens: Vec<&Token> = children
+        .iter()
+        .filter(|token| !matches!(token, Token::Text(text) if text.trim().is_empty()))
+        .collect();
+
+    match tokens.as_slice() {
+        [] => Some(XmpValue::Text(String::new())),
+        [Token::Text(text)] => Some(XmpValue::Text(text.clone())),
+        [Token::Start {
+            name: container,
+            empty: false,
+            ..
+        }, items @ .., Token::End(_)] => {
+            let mut values = Vec::new();
+            let mut rest = items;
+            while !rest.is_empty() {
+                let (lang, text, next) = match rest {
+                    [Token::Start {
+                        name: "rdf:li",
+                        attributes,
+                        empty: false,
+                    }, Token::Text(text), Token::End("rdf:li"), next @ ..] => (
+                        attributes,
+                        text.clone(),
+                        next,
+                    ),
+                    [Token::Start {
+                        name: "rdf:li",
+                        attributes,
+                        empty: false,
+                    }, Token::End("rdf:li"), next @ ..] => (
+                        attributes,
+                        String::new(),
+                        next,
+                    ),
+                    _ => return None,
+                };
+                let lang = lang
+                    .iter()
+                    .find(|(name, _)| *name == "xml:lang")
+                    .map(|(_, lang)| lang.clone())
+                    .unwrap_or_else(|| "x-default".to_string());
+                values.push((lang, text));
+                rest = next;
+            }
+            let texts = || {
+                values
+                    .iter()
+                    .map(|(_, text)| text.clone())
+                    .collect()
+            };
+            match *container {
+                "rdf:Seq" => Some(XmpValue::Seq(texts())),
+                "rdf:Bag" => Some(XmpValue::Bag(texts())),
+                "rdf:Alt" => Some(XmpValue::Alt(values)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn is_syntax_attribute(name: &str) -> bool {
+    name == "xmlns"
+        || name.starts_with("xmlns:")
+        || name.starts_with("rdf:")
+        || name.starts_with("xml:")
+}
+
+fn escape(text: &str) -> String {
+    text.replace(
+        '&',
+        "&amp;",
+    )
+    .replace(
+        '<',
+        "&lt;",
+    )
+    .replace(
+        '>',
+        "&gt;",
+    )
+    .replace(
+        '"',
+        "&quot;",
+    )
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semicolon) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semicolon];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| {
+                    entity
+                        .strip_prefix('#')
+                        .and_then(
+                            |decimal| {
+                                decimal
+                                    .parse()
+                                    .ok()
+                            },
+                        )
+                })
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semicolon + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+enum Token<'a> {
+    Start {
+        name: &'a str,
+        attributes: Vec<(
+            &'a str,
+            String,
+        )>,
+        empty: bool,
+    },
+    End(&'a str),
+    Text(String),
+}
+
+/// A minimal XML reader for RDF/XML. Comments, processing instructions and declarations are skipped.
+struct Tokenizer<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn skip_past(&mut self, end: &str) -> Result<()> {
+        match self.xml[self.pos..].find(end) {
+            Some(found) => {
+                self.pos += found + end.len();
+                Ok(())
+            }
+            None => Err(Error::IOError("Unexpected end of XMP data".to_string())),
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<Token<'a>>> {
+        loop {
+            let rest = &self.xml[self.pos..];
+            if rest.is_empty() {
+                return Ok(None);
+            }
+
+            if rest.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata
+                    .find("]]>")
+                    .ok_or(Error::IOError("Unexpected end of XMP data".to_string()))?;
+                self.pos += 9 + end + 3;
+                return Ok(Some(
+                    Token::Text(cdata[..end].to_string()),
+                ));
+            } else if rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else if let Some(end_tag) = rest.strip_prefix("</") {
+                let end = end_tag
+                    .find('>')
+                    .ok_or(Error::IOError("Unexpected end of XMP data".to_string()))?;
+                self.pos += 2 + end + 1;
+                return Ok(Some(
+                    Token::End(end_tag[..end].trim()),
+                ));
+            } else if rest.starts_with('<') {
+                return self
+                    .start_tag()
+                    .map(Some);
+            } else {
+                let end = rest
+                    .find('<')
+                    .unwrap_or(rest.len());
+                self.pos += end;
+                return Ok(Some(
+                    Token::Text(unescape(
+                        &rest[..end],
+                    )),
+                ));
+            }
+        }
+    }
+
+    fn start_tag(&mut self) -> Result<Token<'a>> {
+        let xml = self.xml;
+        let invalid = || Error::IOError("Invalid XMP data".to_string());
+        let mut pos = self.pos + 1;
+        let name_end = xml[pos..]
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .ok_or_else(invalid)?
+            + pos;
+        let name = &xml[pos..name_end];
+        pos = name_end;
+
+        let mut attributes = Vec::new();
+        loop {
+            let trimmed = xml[pos..].trim_start();
+            if let Some(after) = trimmed.strip_prefix("/>") {
+                self.pos = xml.len() - after.len();
+                return Ok(
+                    Token::Start {
+                        name,
+                        attributes,
+                        empty: true,
+                    },
+                );
+            }
+            if let Some(after) = trimmed.strip_prefix('>') {
+                self.pos = xml.len() - after.len();
+                return Ok(
+                    Token::Start {
+                        name,
+                        attributes,
+                        empty: false,
+                    },
+                );
+            }
+            let equals = trimmed
+                .find('=')
+                .ok_or_else(invalid)?;
+            let attribute = trimmed[..equals].trim();
+            let value = trimmed[equals + 1..].trim_start();
+            let quote = value
+                .chars()
+                .next()
+                .filter(|quote| *quote == '"' || *quote == '\'')
+                .ok_or_else(invalid)?;
+            let value_end = value[1..]
+                .find(quote)
+                .ok_or_else(invalid)?;
+            attributes.push((
+                attribute,
+                unescape(&value[1..1 + value_end]),
+            ));
+            pos = xml.len() - value[1 + value_end + 1..].len();
+        }
+    }
+}
+
+impl VipsImage {
+    /// Returns the properties of the `xmp-data` item.
+    pub fn xmp(&self) -> Result<Xmp> {
+        Xmp::parse(&self.get_blob(XMP_DATA)?)
+    }
+
+    /// Replaces the `xmp-data` item with the serialized properties.
+    pub fn set_xmp(&mut self, xmp: &Xmp) -> Result<()> {
+        self.set_blob_copy(
+            XMP_DATA,
+            &xmp.to_bytes(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKET: &str = r#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/" x:xmptk="Adobe XMP Core 5.6-c140">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about="uuid:faf5bdd5-ba3d-11da-ad31-d33d75182f1b"
+    xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+    xmp:CreatorTool="Editor &amp; Co">
+   <xmp:Rating>5</xmp:Rating>
+  </rdf:Description>
+  <rdf:Description rdf:about="uuid:faf5bdd5-ba3d-11da-ad31-d33d75182f1b"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:Iptc4xmpCore="http://iptc.org/std/Iptc4xmpCore/1.0/xmlns/">
+   <dc:creator>
+    <rdf:Seq>
+     <rdf:li>Jane &lt;Doe&gt;</rdf:li>
+    </rdf:Seq>
+   </dc:creator>
+   <dc:subject>
+    <rdf:Bag>
+     <rdf:li>sea</rdf:li>
+     <rdf:li>sky</rdf:li>
+    </rdf:Bag>
+   </dc:subject>
+   <dc:rights>
+    <rdf:Alt>
+     <rdf:li xml:lang="x-default">© 2024 Jane</rdf:li>
+     <rdf:li xml:lang="fr">© 2024 Jeanne</rdf:li>
+    </rdf:Alt>
+   </dc:rights>
+   <Iptc4xmpCore:CreatorContactInfo rdf:parseType="Resource">
+    <Iptc4xmpCore:CiEmailWork>jane@example.com</Iptc4xmpCore:CiEmailWork>
+   </Iptc4xmpCore:CreatorContactInfo>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>"#;
+
+    #[test]
+    fn parse_values() {
+        let xmp = Xmp::parse(PACKET.as_bytes()).unwrap();
+        assert_eq!(
+            xmp.toolkit
+                .as_deref(),
+            Some("Adobe XMP Core 5.6-c140")
+        );
+        assert_eq!(
+            xmp.about
+                .as_deref(),
+            Some("uuid:faf5bdd5-ba3d-11da-ad31-d33d75182f1b")
+        );
+        assert_eq!(
+            xmp.text("xmp:CreatorTool"),
+            Some("Editor & Co")
+        );
+        assert_eq!(
+            xmp.text("xmp:Rating"),
+            Some("5")
+        );
+        assert_eq!(
+            xmp.get("dc:creator"),
+            Some(
+                &XmpValue::Seq(vec![
+                    "Jane <Doe>".to_string()
+                ])
+            )
+        );
+        assert_eq!(
+            xmp.get("dc:subject"),
+            Some(
+                &XmpValue::Bag(vec![
+                    "sea".to_string(),
+                    "sky".to_string()
+                ])
+            )
+        );
+        assert_eq!(
+            xmp.text("dc:rights"),
+            Some("© 2024 Jane")
+        );
+        assert!(matches!(
+            xmp.get("Iptc4xmpCore:CreatorContactInfo"),
+            Some(XmpValue::Raw(_))
+        ));
+    }
+
+    #[test]
+    fn round_trip() {
+        let xmp = Xmp::parse(PACKET.as_bytes()).unwrap();
+        let bytes = xmp.to_bytes();
+        let parsed = Xmp::parse(&bytes).unwrap();
+        assert_eq!(
+            parsed.toolkit,
+            xmp.toolkit
+        );
+        assert_eq!(
+            parsed.about,
+            xmp.about
+        );
+        assert_eq!(
+            parsed.properties(),
+            xmp.properties()
+        );
+        assert_eq!(
+            parsed.to_bytes(),
+            bytes
+        );
+    }
+
+    #[test]
+    fn round_trip_new_packet() {
+        let xmp = Xmp::new()
+            .with_creator(&["Jane", "John"])
+            .with_rights("\"Quoted\" & <tagged>")
+            .with_marked(true)
+            .with_credit("Agency");
+        let parsed = Xmp::parse(&xmp.to_bytes()).unwrap();
+        assert_eq!(
+            parsed.properties(),
+            xmp.properties()
+        );
+        assert_eq!(
+            parsed.text("dc:rights"),
+            Some("\"Quoted\" & <tagged>")
+        );
+    }
+
+    #[test]
+    fn malformed_input_is_an_error() {
+        assert!(Xmp::parse(&[0xFF, 0xFE]).is_err());
+        assert!(Xmp::parse(b"<x:xmpmeta><rdf:RDF><rdf:Description><dc:title>").is_err());
+    }
+}